@@ -0,0 +1,73 @@
+//! Decoder benchmarks over the canonical test vectors
+//!
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use ruuvi_decoders::mac::mac_hex_into;
+use ruuvi_decoders::{decode, decode_bytes, decode_hex_into, extract_ruuvi_from_ble};
+
+const V5: &str = "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+const V6: &str = "06170C5668C79E007000C90501D9FFCD004C884F";
+const E1: &str = "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F";
+const BLE_V5: &str = "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (name, payload) in [("v5", V5), ("v6", V6), ("e1", E1)] {
+        let ble_data = format!("9904{payload}");
+        group.bench_function(name, |b| b.iter(|| decode(black_box(&ble_data))));
+    }
+    group.bench_function("ble_advertisement", |b| {
+        b.iter(|| decode(black_box(BLE_V5)));
+    });
+    group.finish();
+}
+
+fn bench_decode_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_bytes");
+    for (name, payload) in [("v5", V5), ("v6", V6), ("e1", E1)] {
+        let bytes = hex::decode(payload).unwrap();
+        group.bench_function(name, |b| b.iter(|| decode_bytes(black_box(&bytes))));
+    }
+    group.finish();
+}
+
+fn bench_hex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hex");
+    // Same payload through the allocating path, for comparison with decode_hex_into
+    group.bench_function("hex_decode_then_decode_bytes", |b| {
+        b.iter(|| decode_bytes(&hex::decode(black_box(V5)).unwrap()));
+    });
+    group.bench_function("decode_hex_into", |b| {
+        let mut buf = [0u8; 64];
+        b.iter(|| decode_hex_into(black_box(V5), &mut buf));
+    });
+    group.bench_function("extract_ruuvi_from_ble", |b| {
+        b.iter(|| extract_ruuvi_from_ble(black_box(BLE_V5)));
+    });
+    group.finish();
+}
+
+fn bench_mac(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mac");
+    let mac = [0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F];
+    group.bench_function("hex_encode", |b| {
+        b.iter(|| hex::encode(black_box(mac)));
+    });
+    group.bench_function("mac_hex_into", |b| {
+        let mut buf = [0u8; 12];
+        b.iter(|| mac_hex_into(black_box(&mac), &mut buf).len());
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_decode,
+    bench_decode_bytes,
+    bench_hex,
+    bench_mac
+);
+criterion_main!(benches);
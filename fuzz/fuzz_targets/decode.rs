@@ -0,0 +1,16 @@
+//! Feed arbitrary input to the public decode API; any panic is a bug
+//!
+//! Run with `cargo +nightly fuzz run decode` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruuvi_decoders::{RuuviData, decode, extract_ruuvi_from_ble};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = RuuviData::decode(data);
+
+    let text = String::from_utf8_lossy(data);
+    let _ = decode(&text);
+    let _ = extract_ruuvi_from_ble(&text);
+});
@@ -0,0 +1,244 @@
+//! Window aggregation of successive readings from one sensor
+//!
+//! Dashboards often downsample many readings into one point per window; the
+//! [`Aggregator`] folds readings into min/max/mean per field.
+
+use serde::{Deserialize, Serialize};
+
+use crate::normalized::NormalizedReading;
+use crate::ruuvi_data::RuuviData;
+
+/// Minimum, maximum and mean of one field over the readings that carried it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FieldSummary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Number of readings that carried this field
+    pub count: usize,
+}
+
+/// Aggregated readings of one sensor
+///
+/// A field is `None` when no reading in the window carried it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
+pub struct AggregateReading {
+    /// MAC address of the sensor, `None` before the first reading
+    pub mac_address: Option<String>,
+    /// Number of readings aggregated
+    pub readings: usize,
+    /// Temperature in Celsius
+    pub temperature: Option<FieldSummary>,
+    /// Humidity in %
+    pub humidity: Option<FieldSummary>,
+    /// Pressure in hPa
+    pub pressure: Option<FieldSummary>,
+    /// PM2.5 in μg/m³ (V6, E1)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm25"))]
+    pub pm2_5: Option<FieldSummary>,
+    /// CO2 concentration in ppm (V6, E1)
+    pub co2: Option<FieldSummary>,
+    /// Luminosity in Lux (V6, E1)
+    pub luminosity: Option<FieldSummary>,
+}
+
+/// Running min/max/sum of one field
+#[derive(Debug, Clone, Copy, Default)]
+struct FieldAccumulator {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: usize,
+}
+
+impl FieldAccumulator {
+    fn push(&mut self, value: Option<f64>) {
+        let Some(value) = value else {
+            return;
+        };
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn summary(&self) -> Option<FieldSummary> {
+        (self.count > 0).then(|| FieldSummary {
+            min: self.min,
+            max: self.max,
+            mean: self.sum / self.count as f64,
+            count: self.count,
+        })
+    }
+}
+
+/// Folds successive readings of one sensor into an [`AggregateReading`]
+///
+/// The first reading fixes the sensor MAC address; readings from other
+/// sensors are rejected. Unavailable fields of a reading are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{aggregate::Aggregator, decode};
+///
+/// let mut aggregator = Aggregator::default();
+/// let data = decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert!(aggregator.push(&data));
+///
+/// let reading = aggregator.finish();
+/// assert_eq!(reading.readings, 1);
+/// assert_eq!(reading.temperature.unwrap().mean, 24.3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Aggregator {
+    mac_address: Option<String>,
+    readings: usize,
+    temperature: FieldAccumulator,
+    humidity: FieldAccumulator,
+    pressure: FieldAccumulator,
+    pm2_5: FieldAccumulator,
+    co2: FieldAccumulator,
+    luminosity: FieldAccumulator,
+}
+
+impl Aggregator {
+    /// Add a reading, returning `false` if it is from a different sensor
+    pub fn push(&mut self, data: &RuuviData) -> bool {
+        let reading = NormalizedReading::from(data.clone());
+
+        match &self.mac_address {
+            Some(mac_address) if *mac_address != reading.mac_address => return false,
+            Some(_) => {}
+            None => self.mac_address = Some(reading.mac_address.clone()),
+        }
+
+        self.readings += 1;
+        self.temperature.push(reading.temperature);
+        self.humidity.push(reading.humidity);
+        self.pressure.push(reading.pressure);
+        self.pm2_5.push(reading.pm2_5);
+        self.co2.push(reading.co2.map(f64::from));
+        self.luminosity.push(reading.luminosity);
+        true
+    }
+
+    /// Number of readings added so far
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.readings
+    }
+
+    /// Whether no reading was added yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.readings == 0
+    }
+
+    /// Summarize the readings added so far
+    #[must_use]
+    pub fn aggregate(&self) -> AggregateReading {
+        AggregateReading {
+            mac_address: self.mac_address.clone(),
+            readings: self.readings,
+            temperature: self.temperature.summary(),
+            humidity: self.humidity.summary(),
+            pressure: self.pressure.summary(),
+            pm2_5: self.pm2_5.summary(),
+            co2: self.co2.summary(),
+            luminosity: self.luminosity.summary(),
+        }
+    }
+
+    /// Summarize the readings and start a new, empty window
+    pub fn finish(&mut self) -> AggregateReading {
+        let reading = self.aggregate();
+        *self = Self::default();
+        reading
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v5::DataFormatV5;
+
+    fn v5(temperature: Option<f64>, humidity: Option<f64>) -> RuuviData {
+        let mut builder = DataFormatV5::builder().mac_address("cbb8334c884f");
+        if let Some(temperature) = temperature {
+            builder = builder.temperature(temperature);
+        }
+        if let Some(humidity) = humidity {
+            builder = builder.humidity(humidity);
+        }
+        RuuviData::V5(builder.build())
+    }
+
+    #[test]
+    fn test_aggregate_window() {
+        let mut aggregator = Aggregator::default();
+        assert!(aggregator.push(&v5(Some(20.0), Some(40.0))));
+        assert!(aggregator.push(&v5(Some(24.0), None)));
+        assert!(aggregator.push(&v5(Some(22.5), Some(50.0))));
+        assert!(aggregator.push(&v5(None, None)));
+
+        let reading = aggregator.aggregate();
+        assert_eq!(reading.mac_address.as_deref(), Some("cbb8334c884f"));
+        assert_eq!(reading.readings, 4);
+        assert_eq!(
+            reading.temperature,
+            Some(FieldSummary {
+                min: 20.0,
+                max: 24.0,
+                mean: 22.166_666_666_666_668,
+                count: 3,
+            })
+        );
+        assert_eq!(
+            reading.humidity,
+            Some(FieldSummary {
+                min: 40.0,
+                max: 50.0,
+                mean: 45.0,
+                count: 2,
+            })
+        );
+        assert_eq!(reading.pressure, None);
+        assert_eq!(reading.co2, None);
+    }
+
+    #[test]
+    fn test_rejects_other_sensor() {
+        let mut aggregator = Aggregator::default();
+        assert!(aggregator.push(&v5(Some(20.0), None)));
+
+        let other = RuuviData::V5(
+            DataFormatV5::builder()
+                .mac_address("aabbccddeeff")
+                .temperature(30.0)
+                .build(),
+        );
+        assert!(!aggregator.push(&other));
+        assert_eq!(aggregator.len(), 1);
+        assert_eq!(aggregator.aggregate().temperature.unwrap().max, 20.0);
+    }
+
+    #[test]
+    fn test_finish_starts_new_window() {
+        let mut aggregator = Aggregator::default();
+        assert!(aggregator.is_empty());
+        aggregator.push(&v5(Some(20.0), None));
+
+        assert_eq!(aggregator.finish().readings, 1);
+        assert!(aggregator.is_empty());
+        assert_eq!(aggregator.aggregate().mac_address, None);
+        assert_eq!(aggregator.aggregate().temperature, None);
+    }
+}
@@ -1,27 +1,298 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DecodeError, Result};
+use crate::ruuvi_data::{DataFormat, RuuviData};
+
+/// Lowest valid Sensirion VOC or `NOx` index; 0 means the index is not available yet
+pub const GAS_INDEX_MIN: u16 = 1;
+/// Highest valid Sensirion VOC or `NOx` index; 511 marks an invalid reading
+pub const GAS_INDEX_MAX: u16 = 500;
+
 const AQI_MAX: f64 = 100.0;
 const PM25_MAX: f64 = 60.0;
 const PM25_MIN: f64 = 0.0;
-const PM25_SCALE: f64 = AQI_MAX / (PM25_MAX - PM25_MIN); // ≈ 1.6667
 const CO2_MAX: f64 = 2300.0;
 const CO2_MIN: f64 = 420.0;
-const CO2_SCALE: f64 = AQI_MAX / (CO2_MAX - CO2_MIN); // ≈ 0.05319
+/// Highest PM2.5 in μg/m³ the particulate matter sensor reports
+const PM25_PLAUSIBLE_MAX: f64 = 1000.0;
+/// Highest CO2 in ppm the CO2 sensor reports
+const CO2_PLAUSIBLE_MAX: u16 = 40000;
 
+/// Input ranges used by [`calc_aqi_with`]
+///
+/// Readings at or below the minimum do not lower the score, readings at or
+/// above the maximum bring the score down to 0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AqiConfig {
+    /// PM2.5 level in μg/m³ considered clean air
+    pub pm25_min: f64,
+    /// PM2.5 level in μg/m³ considered worst air
+    pub pm25_max: f64,
+    /// CO2 level in ppm considered clean air
+    pub co2_min: f64,
+    /// CO2 level in ppm considered worst air
+    pub co2_max: f64,
+}
+
+impl Default for AqiConfig {
+    fn default() -> Self {
+        Self {
+            pm25_min: PM25_MIN,
+            pm25_max: PM25_MAX,
+            co2_min: CO2_MIN,
+            co2_max: CO2_MAX,
+        }
+    }
+}
+
+/// Calculate the air quality score (0..100, higher is better) with the default ranges
 #[must_use]
 pub fn calc_aqi(pm2_5: f64, co2: u16) -> f64 {
-    let pm2_5 = pm2_5.clamp(PM25_MIN, PM25_MAX);
-    let co2 = f64::from(co2).clamp(CO2_MIN, CO2_MAX);
+    calc_aqi_with(&AqiConfig::default(), pm2_5, co2)
+}
+
+/// Calculate the air quality score (0..100, higher is better) with custom ranges
+#[must_use]
+pub fn calc_aqi_with(config: &AqiConfig, pm2_5: f64, co2: u16) -> f64 {
+    let pm25_scale = AQI_MAX / (config.pm25_max - config.pm25_min); // ≈ 1.6667 by default
+    let co2_scale = AQI_MAX / (config.co2_max - config.co2_min); // ≈ 0.05319 by default
 
-    let dx = (pm2_5 - PM25_MIN) * PM25_SCALE; // 0..100
-    let dy = (co2 - CO2_MIN) * CO2_SCALE; // 0..100
+    let pm2_5 = pm2_5.clamp(config.pm25_min, config.pm25_max);
+    let co2 = f64::from(co2).clamp(config.co2_min, config.co2_max);
+
+    let dx = (pm2_5 - config.pm25_min) * pm25_scale; // 0..100
+    let dy = (co2 - config.co2_min) * co2_scale; // 0..100
     let r = f64::hypot(dx, dy); // sqrt(dx*dx + dy*dy)
 
     (AQI_MAX - r).clamp(0.0, AQI_MAX)
 }
 
+/// Calculate the air quality score like [`calc_aqi`], rejecting implausible inputs
+///
+/// [`calc_aqi`] clamps its inputs, so a faulty sensor reporting 50000 ppm
+/// scores the same as a genuine 2300 ppm. This fails instead when the inputs
+/// are outside what the sensors can report.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::air_quality::{calc_aqi, try_calc_aqi};
+///
+/// assert_eq!(try_calc_aqi(11.2, 600), Ok(calc_aqi(11.2, 600)));
+/// assert!(try_calc_aqi(11.2, 50000).is_err());
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::ValidationFailed` - PM2.5 is negative, above 1000 μg/m³ or
+///   not a number, or CO2 is above 40000 ppm
+pub fn try_calc_aqi(pm2_5: f64, co2: u16) -> Result<f64> {
+    if !(0.0..=PM25_PLAUSIBLE_MAX).contains(&pm2_5) {
+        return Err(DecodeError::ValidationFailed(format!(
+            "pm2_5 {pm2_5} outside 0..={PM25_PLAUSIBLE_MAX}"
+        )));
+    }
+    if co2 > CO2_PLAUSIBLE_MAX {
+        return Err(DecodeError::ValidationFailed(format!(
+            "co2 {co2} outside 0..={CO2_PLAUSIBLE_MAX}"
+        )));
+    }
+    Ok(calc_aqi(pm2_5, co2))
+}
+
+/// Validate a reassembled 9-bit VOC or `NOx` index
+///
+/// Only `GAS_INDEX_MIN..=GAS_INDEX_MAX` is a reading. 0 is sent while the
+/// sensor's index algorithm is still initializing, 511 when the sensor is
+/// unavailable, and 501 to 510 are not defined by the formats.
+pub(crate) fn gas_index(raw: u16) -> Option<u16> {
+    (GAS_INDEX_MIN..=GAS_INDEX_MAX)
+        .contains(&raw)
+        .then_some(raw)
+}
+
+/// The higher of the Sensirion VOC and `NOx` indexes, as a single number
+///
+/// This is a raw max of two differently scaled indexes: the VOC index
+/// averages 100 in typical conditions while the `NOx` index sits at 1, so a
+/// `NOx` event is hidden unless it exceeds the current VOC index, and a
+/// normal VOC level of 100 reads like a marked `NOx` event. Compare the
+/// indexes separately to judge air quality. When only one index is available
+/// it is returned as is, and `None` is returned when both are missing.
+#[must_use]
+pub fn combined_gas_index(voc_index: Option<u16>, nox_index: Option<u16>) -> Option<u16> {
+    match (voc_index, nox_index) {
+        (Some(voc), Some(nox)) => Some(voc.max(nox)),
+        (voc, nox) => voc.or(nox),
+    }
+}
+
+/// US EPA Air Quality Index category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AqiCategory {
+    /// AQI 0 to 50
+    Good,
+    /// AQI 51 to 100
+    Moderate,
+    /// AQI 101 to 150
+    UnhealthyForSensitiveGroups,
+    /// AQI 151 to 200
+    Unhealthy,
+    /// AQI 201 to 300
+    VeryUnhealthy,
+    /// AQI 301 and above
+    Hazardous,
+}
+
+impl AqiCategory {
+    /// Official EPA display color as an uppercase `#RRGGBB` string
+    #[must_use]
+    pub fn color_hex(&self) -> &'static str {
+        match self {
+            Self::Good => "#00E400",
+            Self::Moderate => "#FFFF00",
+            Self::UnhealthyForSensitiveGroups => "#FF7E00",
+            Self::Unhealthy => "#FF0000",
+            Self::VeryUnhealthy => "#8F3F97",
+            Self::Hazardous => "#7E0023",
+        }
+    }
+}
+
+/// Indoor CO2 level classification following common IAQ guidance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Co2Level {
+    /// Below 800 ppm
+    Good,
+    /// 800 to 1199 ppm
+    Moderate,
+    /// 1200 ppm and above
+    Poor,
+}
+
+/// Classify a CO2 concentration in ppm
+#[must_use]
+pub fn co2_level(ppm: u16) -> Co2Level {
+    match ppm {
+        0..800 => Co2Level::Good,
+        800..1200 => Co2Level::Moderate,
+        _ => Co2Level::Poor,
+    }
+}
+
+/// US EPA PM2.5 breakpoints as revised in 2024: concentration low/high in
+/// μg/m³, index low/high
+const PM25_BREAKPOINTS: [(f64, f64, u32, u32); 6] = [
+    (0.0, 9.0, 0, 50),
+    (9.1, 35.4, 51, 100),
+    (35.5, 55.4, 101, 150),
+    (55.5, 125.4, 151, 200),
+    (125.5, 225.4, 201, 300),
+    (225.5, 325.4, 301, 500),
+];
+
+/// US EPA Air Quality Index (0..500, higher is worse) for a PM2.5 concentration in μg/m³
+///
+/// Uses the EPA breakpoints in force since the 2024 PM2.5 revision (Good is
+/// 0..=9.0 μg/m³), truncating the concentration to 0.1 μg/m³ first.
+/// Concentrations above the top breakpoint of 325.4 μg/m³ are capped at 500.
+/// This is the standard index, unlike the custom score of [`calc_aqi`]; map it
+/// to a category with [`aqi_category`].
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn us_aqi_pm25(pm2_5: f64) -> u32 {
+    // The epsilon keeps e.g. 35.4, decoded as 354 * 0.1 = 35.39999..., from truncating to 35.3
+    let concentration = ((pm2_5.max(0.0) * 10.0 + 1e-9).floor() / 10.0).min(325.4);
+
+    PM25_BREAKPOINTS
+        .iter()
+        .find(|(_, c_high, _, _)| concentration <= *c_high)
+        .map_or(500, |&(c_low, c_high, i_low, i_high)| {
+            let slope = f64::from(i_high - i_low) / (c_high - c_low);
+            (slope * (concentration - c_low) + f64::from(i_low)).round() as u32
+        })
+}
+
+/// Map a US EPA AQI value (0..500, higher is worse) to its category
+///
+/// This expects an index on the EPA scale, not the 0..100 score returned by
+/// [`calc_aqi`] where higher is better. Each band includes its upper bound, so
+/// 50.0 is `Good` and anything above it up to 100.0 is `Moderate`.
+#[must_use]
+pub fn aqi_category(score: f64) -> AqiCategory {
+    match score {
+        s if s <= 50.0 => AqiCategory::Good,
+        s if s <= 100.0 => AqiCategory::Moderate,
+        s if s <= 150.0 => AqiCategory::UnhealthyForSensitiveGroups,
+        s if s <= 200.0 => AqiCategory::Unhealthy,
+        s if s <= 300.0 => AqiCategory::VeryUnhealthy,
+        _ => AqiCategory::Hazardous,
+    }
+}
+
+/// Air quality fields of a V6 or E1 reading, without matching on the format
+///
+/// Data Format 6 only measures PM2.5, so its `pm1_0`, `pm4_0` and `pm10_0`
+/// are always `None`.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::air_quality::AirQualityReading;
+///
+/// let data = ruuvi_decoders::decode("17FF990406170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+/// let reading = AirQualityReading::try_from(&data).unwrap();
+/// assert_eq!(reading.co2, Some(201));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AirQualityReading {
+    /// PM1.0 in μg/m³
+    pub pm1_0: Option<f64>,
+    /// PM2.5 in μg/m³
+    pub pm2_5: Option<f64>,
+    /// PM4.0 in μg/m³
+    pub pm4_0: Option<f64>,
+    /// PM10.0 in μg/m³
+    pub pm10_0: Option<f64>,
+    /// CO2 in ppm
+    pub co2: Option<u16>,
+    /// VOC index (unitless)
+    pub voc_index: Option<u16>,
+    /// `NOx` index (unitless)
+    pub nox_index: Option<u16>,
+}
+
+impl TryFrom<&RuuviData> for AirQualityReading {
+    type Error = DecodeError;
+
+    /// # Errors
+    ///
+    /// * `DecodeError::UnsupportedFormat` - Data Format 5, which has no air quality sensors
+    fn try_from(data: &RuuviData) -> std::result::Result<Self, Self::Error> {
+        match data {
+            RuuviData::V5(_) => Err(DecodeError::UnsupportedFormat(DataFormat::V5 as u8)),
+            RuuviData::V6(v6) => Ok(Self {
+                pm2_5: v6.pm2_5,
+                co2: v6.co2,
+                voc_index: v6.voc_index,
+                nox_index: v6.nox_index,
+                ..Self::default()
+            }),
+            RuuviData::E1(e1) => Ok(Self {
+                pm1_0: e1.pm1_0,
+                pm2_5: e1.pm2_5,
+                pm4_0: e1.pm4_0,
+                pm10_0: e1.pm10_0,
+                co2: e1.co2,
+                voc_index: e1.voc_index,
+                nox_index: e1.nox_index,
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::u16;
-
     use super::*;
     use rstest::rstest;
 
@@ -53,6 +324,114 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(0.0, 0)]
+    #[case(30.0, 1360)]
+    #[case(1000.0, 40000)]
+    fn try_calc_aqi_matches_calc_aqi(#[case] pm2_5: f64, #[case] co2: u16) {
+        assert_eq!(try_calc_aqi(pm2_5, co2), Ok(calc_aqi(pm2_5, co2)));
+    }
+
+    #[rstest]
+    #[case(-0.1, 600, "pm2_5 -0.1 outside 0..=1000")]
+    #[case(1000.1, 600, "pm2_5 1000.1 outside 0..=1000")]
+    #[case(f64::NAN, 600, "pm2_5 NaN outside 0..=1000")]
+    #[case(10.0, 40001, "co2 40001 outside 0..=40000")]
+    #[case(10.0, 50000, "co2 50000 outside 0..=40000")]
+    fn try_calc_aqi_rejects_implausible(
+        #[case] pm2_5: f64,
+        #[case] co2: u16,
+        #[case] message: &str,
+    ) {
+        assert_eq!(
+            try_calc_aqi(pm2_5, co2),
+            Err(DecodeError::ValidationFailed(message.into()))
+        );
+    }
+
+    #[rstest]
+    #[case(0, Co2Level::Good)]
+    #[case(799, Co2Level::Good)]
+    #[case(800, Co2Level::Moderate)]
+    #[case(1199, Co2Level::Moderate)]
+    #[case(1200, Co2Level::Poor)]
+    #[case(u16::MAX, Co2Level::Poor)]
+    fn co2_level_boundaries(#[case] ppm: u16, #[case] expected: Co2Level) {
+        assert_eq!(co2_level(ppm), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, 0)]
+    #[case(9.0, 50)]
+    #[case(9.1, 51)]
+    #[case(9.04, 50)] // truncated to 9.0
+    #[case(12.0, 56)] // Good before the 2024 revision
+    #[case(35.4, 100)]
+    #[case(35.5, 101)]
+    #[case(55.4, 150)]
+    #[case(55.5, 151)]
+    #[case(100.0, 182)]
+    #[case(125.4, 200)]
+    #[case(125.5, 201)]
+    #[case(225.4, 300)]
+    #[case(225.5, 301)]
+    #[case(325.4, 500)]
+    #[case(500.4, 500)]
+    #[case(999.9, 500)]
+    #[case(-1.0, 0)]
+    fn us_aqi_pm25_breakpoints(#[case] pm2_5: f64, #[case] expected: u32) {
+        assert_eq!(us_aqi_pm25(pm2_5), expected);
+    }
+
+    #[rstest]
+    #[case(0, None)]
+    #[case(1, Some(1))]
+    #[case(500, Some(500))]
+    #[case(501, None)]
+    #[case(511, None)]
+    fn gas_index_validity(#[case] raw: u16, #[case] expected: Option<u16>) {
+        assert_eq!(gas_index(raw), expected);
+    }
+
+    #[rstest]
+    #[case(Some(100), Some(1), Some(100))]
+    #[case(Some(100), Some(80), Some(100))] // NOx event hidden by a typical VOC level
+    #[case(Some(20), Some(250), Some(250))]
+    #[case(Some(42), None, Some(42))]
+    #[case(None, Some(7), Some(7))]
+    #[case(None, None, None)]
+    fn combined_gas_index_cases(
+        #[case] voc: Option<u16>,
+        #[case] nox: Option<u16>,
+        #[case] expected: Option<u16>,
+    ) {
+        assert_eq!(combined_gas_index(voc, nox), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, 420)]
+    #[case(30.0, 1360)]
+    #[case(12.5, 800)]
+    fn calc_aqi_uses_default_config(#[case] pm2_5: f64, #[case] co2: u16) {
+        let got = calc_aqi_with(&AqiConfig::default(), pm2_5, co2);
+        assert!((got - calc_aqi(pm2_5, co2)).abs() < EPS);
+    }
+
+    #[test]
+    fn calc_aqi_with_custom_config() {
+        let config = AqiConfig {
+            pm25_max: 35.0,
+            co2_max: 1420.0,
+            ..AqiConfig::default()
+        };
+        // Reaching both custom maximums gives the worst score
+        assert!((calc_aqi_with(&config, 35.0, 1420) - 0.0).abs() < EPS);
+        // Half way along both axes: r = hypot(50, 50)
+        assert!((calc_aqi_with(&config, 17.5, 920) - 29.289_321_881_345_24).abs() < EPS);
+        // Same reading scores worse under the stricter config
+        assert!(calc_aqi_with(&config, 17.5, 920) < calc_aqi(17.5, 920));
+    }
+
     #[test]
     fn smoke_properties() {
         // Explicit clamping assertions
@@ -67,4 +446,66 @@ mod tests {
             "co2 above maximum should be clamped to CO2_MAX producing AQI 0"
         );
     }
+
+    #[rstest]
+    #[case(0.0, AqiCategory::Good)]
+    #[case(50.0, AqiCategory::Good)]
+    #[case(50.1, AqiCategory::Moderate)]
+    #[case(100.0, AqiCategory::Moderate)]
+    #[case(100.1, AqiCategory::UnhealthyForSensitiveGroups)]
+    #[case(150.0, AqiCategory::UnhealthyForSensitiveGroups)]
+    #[case(150.1, AqiCategory::Unhealthy)]
+    #[case(200.0, AqiCategory::Unhealthy)]
+    #[case(200.1, AqiCategory::VeryUnhealthy)]
+    #[case(300.0, AqiCategory::VeryUnhealthy)]
+    #[case(300.1, AqiCategory::Hazardous)]
+    #[case(500.0, AqiCategory::Hazardous)]
+    fn aqi_category_boundaries(#[case] score: f64, #[case] expected: AqiCategory) {
+        assert_eq!(aqi_category(score), expected);
+    }
+
+    #[rstest]
+    #[case(AqiCategory::Good, "#00E400")]
+    #[case(AqiCategory::Moderate, "#FFFF00")]
+    #[case(AqiCategory::UnhealthyForSensitiveGroups, "#FF7E00")]
+    #[case(AqiCategory::Unhealthy, "#FF0000")]
+    #[case(AqiCategory::VeryUnhealthy, "#8F3F97")]
+    #[case(AqiCategory::Hazardous, "#7E0023")]
+    fn aqi_category_colors(#[case] category: AqiCategory, #[case] expected: &str) {
+        assert_eq!(category.color_hex(), expected);
+    }
+
+    #[test]
+    fn air_quality_reading_from_ruuvi_data() {
+        let v5 = crate::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+            .unwrap();
+        assert_eq!(
+            AirQualityReading::try_from(&v5),
+            Err(DecodeError::UnsupportedFormat(0x05))
+        );
+
+        let v6 = crate::decode("17FF990406170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        assert_eq!(
+            AirQualityReading::try_from(&v6),
+            Ok(AirQualityReading {
+                pm2_5: Some(11.200000000000001),
+                co2: Some(201),
+                voc_index: Some(10),
+                nox_index: Some(2),
+                ..AirQualityReading::default()
+            })
+        );
+
+        let e1 = crate::decode(
+            "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let RuuviData::E1(ref fields) = e1 else {
+            unreachable!();
+        };
+        let reading = AirQualityReading::try_from(&e1).unwrap();
+        assert_eq!(reading.pm1_0, fields.pm1_0);
+        assert_eq!(reading.pm10_0, fields.pm10_0);
+        assert_eq!(reading.nox_index, fields.nox_index);
+    }
 }
@@ -0,0 +1,138 @@
+//! Decode a batch of inputs, keeping failures traceable to their input
+
+use crate::error::DecodeError;
+use crate::ruuvi_data::RuuviData;
+
+/// Outcome of [`decode_batch`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchResult {
+    /// Decoded readings, in input order
+    pub decoded: Vec<RuuviData>,
+    /// Index into the input and error of every input that failed, in input order
+    pub failures: Vec<(usize, DecodeError)>,
+}
+
+impl BatchResult {
+    /// Number of inputs in the batch
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.decoded.len() + self.failures.len()
+    }
+
+    /// Number of inputs that decoded
+    #[must_use]
+    pub fn succeeded(&self) -> usize {
+        self.decoded.len()
+    }
+
+    /// Number of inputs that failed to decode
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Whether every input decoded, `true` for an empty batch
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Input indices of the readings in `decoded`, in the same order
+    pub fn decoded_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut failed = self.failures.iter().map(|(index, _)| *index).peekable();
+        (0..self.total()).filter(move |index| {
+            if failed.peek() == Some(index) {
+                failed.next();
+                false
+            } else {
+                true
+            }
+        })
+    }
+}
+
+/// Decode every input with [`crate::decode`], collecting readings and failures
+///
+/// # Arguments
+///
+/// * `inputs` - BLE advertisement hex strings, as accepted by [`crate::decode`]
+///
+/// # Returns
+///
+/// A [`BatchResult`] with the readings in input order and each failure paired
+/// with the index of its input
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_batch, DecodeError};
+///
+/// let result = decode_batch(&[
+///     "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+///     "",
+///     "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+/// ]);
+/// assert_eq!(result.succeeded(), 2);
+/// assert_eq!(result.failures, [(1, DecodeError::NoData)]);
+/// ```
+#[must_use]
+pub fn decode_batch(inputs: &[&str]) -> BatchResult {
+    let mut result = BatchResult::default();
+    for (index, input) in inputs.iter().enumerate() {
+        match crate::decode(input) {
+            Ok(data) => result.decoded.push(data),
+            Err(err) => result.failures.push((index, err)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataFormat;
+
+    #[test]
+    fn test_decode_batch() {
+        let result = decode_batch(&[
+            "not hex",
+            "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "0201061BFF99040512FC5394C37C",
+            "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+            "020106",
+        ]);
+
+        assert_eq!(result.total(), 5);
+        assert_eq!(result.succeeded(), 2);
+        assert_eq!(result.failed(), 3);
+        assert!(!result.is_complete());
+        assert_eq!(
+            result
+                .decoded
+                .iter()
+                .map(RuuviData::format)
+                .collect::<Vec<_>>(),
+            [DataFormat::V5, DataFormat::V6]
+        );
+        assert_eq!(
+            result
+                .failures
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            [0, 2, 4]
+        );
+        assert!(matches!(result.failures[0].1, DecodeError::InvalidHex(_)));
+        assert_eq!(result.failures[2].1, DecodeError::MissingManufacturerId);
+        assert_eq!(result.decoded_indices().collect::<Vec<_>>(), [1, 3]);
+    }
+
+    #[test]
+    fn test_decode_batch_empty() {
+        let result = decode_batch(&[]);
+        assert_eq!(result, BatchResult::default());
+        assert_eq!(result.total(), 0);
+        assert!(result.is_complete());
+        assert_eq!(result.decoded_indices().count(), 0);
+    }
+}
@@ -0,0 +1,209 @@
+//! BLE advertising data (AD) structure parsing
+//!
+//! An advertisement is a sequence of AD structures, each laid out as
+//! `<length> <type> <data...>` where `length` counts the type byte and the data.
+//!
+//! Also provides a rough RSSI based distance estimate for received advertisements.
+
+/// AD type of the Flags structure
+pub const AD_TYPE_FLAGS: u8 = 0x01;
+/// AD type of the Complete Local Name structure
+pub const AD_TYPE_COMPLETE_LOCAL_NAME: u8 = 0x09;
+/// AD type of the Manufacturer Specific Data structure
+pub const AD_TYPE_MANUFACTURER_DATA: u8 = 0xFF;
+/// Bluetooth SIG company identifier of Ruuvi Innovations
+pub const RUUVI_COMPANY_ID: u16 = 0x0499;
+
+/// A single AD structure borrowed from the advertisement bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdStructure<'a> {
+    /// AD type, e.g. [`AD_TYPE_MANUFACTURER_DATA`]
+    pub ad_type: u8,
+    /// AD data following the type byte
+    pub data: &'a [u8],
+}
+
+impl<'a> AdStructure<'a> {
+    /// Split Manufacturer Specific Data into its company ID and payload
+    ///
+    /// Returns `None` for other AD types or when the company ID is truncated.
+    #[must_use]
+    pub fn manufacturer_data(&self) -> Option<(u16, &'a [u8])> {
+        if self.ad_type != AD_TYPE_MANUFACTURER_DATA {
+            return None;
+        }
+
+        match self.data {
+            // Company ID is little-endian
+            [lo, hi, payload @ ..] => Some((u16::from_le_bytes([*lo, *hi]), payload)),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the AD structures of an advertisement
+///
+/// Iteration stops at a zero length byte (start of padding) or at a structure
+/// whose length runs past the end of the input, so malformed advertisements
+/// never read out of bounds.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::ble::{AdStructures, AD_TYPE_FLAGS};
+///
+/// let bytes = hex::decode("020106030316910255AA").unwrap();
+/// let flags = AdStructures::parse(&bytes).find(|ad| ad.ad_type == AD_TYPE_FLAGS);
+/// assert_eq!(flags.map(|ad| ad.data), Some(&[0x06][..]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdStructures<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> AdStructures<'a> {
+    /// Start parsing the AD structures in `bytes`
+    #[must_use]
+    pub fn parse(bytes: &'a [u8]) -> Self {
+        Self { remaining: bytes }
+    }
+}
+
+impl<'a> Iterator for AdStructures<'a> {
+    type Item = AdStructure<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&length, rest) = self.remaining.split_first()?;
+        let length = usize::from(length);
+
+        if length == 0 || length > rest.len() {
+            self.remaining = &[];
+            return None;
+        }
+
+        let (structure, rest) = rest.split_at(length);
+        self.remaining = rest;
+
+        Some(AdStructure {
+            ad_type: structure[0],
+            data: &structure[1..],
+        })
+    }
+}
+
+/// Path loss exponent of free space, use 2.7 to 4.0 indoors
+pub const PATH_LOSS_EXPONENT_FREE_SPACE: f64 = 2.0;
+
+/// Rough distance in metres from the received signal strength
+///
+/// Uses the log-distance path loss model `d = 10^((tx - rssi) / (10 * n))`,
+/// taking the advertised TX power (e.g. `DataFormatV5::tx_power`) as the
+/// signal strength expected at 1 m. The result is only an estimate: walls,
+/// bodies and antenna orientation easily change RSSI by 10 dB.
+///
+/// # Arguments
+///
+/// * `tx_power_dbm` - Advertised TX power in dBm
+/// * `rssi_dbm` - Received signal strength in dBm, e.g. `RuuviGatewayEvent::rssi`
+/// * `path_loss_exponent` - Environment factor `n`, see [`PATH_LOSS_EXPONENT_FREE_SPACE`]
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::ble::{estimate_distance_m, PATH_LOSS_EXPONENT_FREE_SPACE};
+///
+/// let distance = estimate_distance_m(4, -16, PATH_LOSS_EXPONENT_FREE_SPACE);
+/// assert!((distance - 10.0).abs() < 1e-9);
+/// ```
+#[must_use]
+pub fn estimate_distance_m(tx_power_dbm: i8, rssi_dbm: i16, path_loss_exponent: f64) -> f64 {
+    let path_loss = f64::from(i16::from(tx_power_dbm) - rssi_dbm);
+    10_f64.powf(path_loss / (10.0 * path_loss_exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_advertisement() {
+        // Flags, Complete Local Name "Ruuvi 884F", Ruuvi manufacturer data
+        let bytes = hex::decode(concat!(
+            "020106",
+            "0B0952757576692038383446",
+            "1BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        ))
+        .unwrap();
+        let structures: Vec<_> = AdStructures::parse(&bytes).collect();
+
+        assert_eq!(structures.len(), 3);
+        assert_eq!(structures[0].ad_type, AD_TYPE_FLAGS);
+        assert_eq!(structures[0].data, [0x06]);
+        assert_eq!(structures[1].ad_type, AD_TYPE_COMPLETE_LOCAL_NAME);
+        assert_eq!(structures[1].data, b"Ruuvi 884F");
+
+        let (company_id, payload) = structures[2].manufacturer_data().unwrap();
+        assert_eq!(company_id, RUUVI_COMPANY_ID);
+        assert_eq!(payload.len(), 24);
+        assert_eq!(payload[0], 0x05);
+    }
+
+    #[test]
+    fn length_past_end_stops_iteration() {
+        // Second structure claims 0x1B bytes but only 3 follow
+        let bytes = hex::decode("0201061BFF9904").unwrap();
+        let structures: Vec<_> = AdStructures::parse(&bytes).collect();
+
+        assert_eq!(structures.len(), 1);
+        assert_eq!(structures[0].ad_type, AD_TYPE_FLAGS);
+    }
+
+    #[test]
+    fn zero_length_stops_iteration() {
+        let bytes = hex::decode("0201060000000000").unwrap();
+        assert_eq!(AdStructures::parse(&bytes).count(), 1);
+    }
+
+    #[test]
+    fn empty_and_truncated_input() {
+        assert_eq!(AdStructures::parse(&[]).count(), 0);
+        assert_eq!(AdStructures::parse(&[0xFF]).count(), 0);
+        assert_eq!(AdStructures::parse(&[0x01]).count(), 0);
+    }
+
+    #[test]
+    fn manufacturer_data_requires_company_id() {
+        let short = AdStructure {
+            ad_type: AD_TYPE_MANUFACTURER_DATA,
+            data: &[0x99],
+        };
+        assert_eq!(short.manufacturer_data(), None);
+
+        let flags = AdStructure {
+            ad_type: AD_TYPE_FLAGS,
+            data: &[0x99, 0x04],
+        };
+        assert_eq!(flags.manufacturer_data(), None);
+    }
+
+    #[rstest]
+    #[case(4, 4, 2.0, 1.0)] // reference distance
+    #[case(-40, -40, 3.0, 1.0)]
+    #[case(4, -16, 2.0, 10.0)]
+    #[case(0, -40, 2.0, 100.0)]
+    #[case(0, -30, 3.0, 10.0)]
+    #[case(0, 20, 2.0, 0.1)] // stronger than the reference
+    fn distance_estimate(
+        #[case] tx_power: i8,
+        #[case] rssi: i16,
+        #[case] exponent: f64,
+        #[case] expected: f64,
+    ) {
+        let distance = estimate_distance_m(tx_power, rssi, exponent);
+        assert!(
+            (distance - expected).abs() < 1e-9,
+            "{distance} != {expected}"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Memoizing decoder for high fan-in aggregation
+//!
+//! In dense deployments the same advertisement is often received by several
+//! gateways within milliseconds. [`CachingDecoder`] remembers the most
+//! recently decoded payloads and returns clones for repeats.
+
+use crate::{Result, RuuviData};
+use std::collections::{HashMap, VecDeque};
+
+/// Decoder that memoizes results of [`crate::decode_bytes`] in a small LRU cache
+///
+/// Errors are cached as well, since decoding the same bytes always fails the
+/// same way.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::cache::CachingDecoder;
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// let mut decoder = CachingDecoder::new(64);
+///
+/// let first = decoder.decode(&bytes);
+/// let second = decoder.decode(&bytes);
+///
+/// assert_eq!(first, second);
+/// assert_eq!((decoder.hits(), decoder.misses()), (1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachingDecoder {
+    capacity: usize,
+    entries: HashMap<Vec<u8>, Result<RuuviData>>,
+    // Least recently used key first
+    recency: VecDeque<Vec<u8>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl CachingDecoder {
+    /// Create a decoder caching up to `capacity` distinct payloads
+    ///
+    /// A capacity of 0 disables caching, every call is then a miss.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: VecDeque::with_capacity(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Decode a raw Ruuvi payload, reusing the cached result for repeated input
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::decode_bytes`]
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<RuuviData> {
+        if let Some(result) = self.entries.get(bytes) {
+            self.hits += 1;
+            let result = result.clone();
+            self.touch(bytes);
+            return result;
+        }
+
+        self.misses += 1;
+        let result = crate::decode_bytes(bytes);
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity
+                && let Some(oldest) = self.recency.pop_front()
+            {
+                self.entries.remove(&oldest);
+            }
+            self.entries.insert(bytes.to_vec(), result.clone());
+            self.recency.push_back(bytes.to_vec());
+        }
+        result
+    }
+
+    /// Mark a cached key as most recently used
+    fn touch(&mut self, bytes: &[u8]) {
+        if let Some(idx) = self.recency.iter().position(|key| key == bytes)
+            && let Some(key) = self.recency.remove(idx)
+        {
+            self.recency.push_back(key);
+        }
+    }
+
+    /// Number of calls answered from the cache
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of calls that had to decode
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Number of cached payloads
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum number of cached payloads
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Drop all cached payloads, keeping the hit and miss counters
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodeError;
+
+    const V5: &str = "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+    const V6: &str = "06170C5668C79E007000C90501D9FFCD004C884F";
+
+    #[test]
+    fn test_identical_input_hits_cache() {
+        let bytes = hex::decode(V5).unwrap();
+        let mut decoder = CachingDecoder::new(4);
+
+        let first = decoder.decode(&bytes);
+        assert_eq!((decoder.hits(), decoder.misses()), (0, 1));
+
+        for _ in 0..3 {
+            assert_eq!(decoder.decode(&bytes), first);
+        }
+        assert_eq!((decoder.hits(), decoder.misses()), (3, 1));
+        assert_eq!(first, crate::decode_bytes(&bytes));
+        assert_eq!(decoder.len(), 1);
+    }
+
+    #[test]
+    fn test_errors_are_cached() {
+        let mut decoder = CachingDecoder::new(4);
+
+        assert_eq!(
+            decoder.decode(&[0x63]),
+            Err(DecodeError::UnsupportedFormat(0x63))
+        );
+        assert_eq!(
+            decoder.decode(&[0x63]),
+            Err(DecodeError::UnsupportedFormat(0x63))
+        );
+        assert_eq!((decoder.hits(), decoder.misses()), (1, 1));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let v5 = hex::decode(V5).unwrap();
+        let v6 = hex::decode(V6).unwrap();
+        let mut decoder = CachingDecoder::new(2);
+
+        let _ = decoder.decode(&v5);
+        let _ = decoder.decode(&v6);
+        let _ = decoder.decode(&v5); // hit, v6 is now least recently used
+        let _ = decoder.decode(&[0x63]); // evicts v6
+        assert_eq!(decoder.len(), 2);
+
+        let _ = decoder.decode(&v5);
+        assert_eq!((decoder.hits(), decoder.misses()), (2, 3));
+        let _ = decoder.decode(&v6);
+        assert_eq!((decoder.hits(), decoder.misses()), (2, 4));
+    }
+
+    #[test]
+    fn test_zero_capacity_never_caches() {
+        let bytes = hex::decode(V5).unwrap();
+        let mut decoder = CachingDecoder::new(0);
+
+        let _ = decoder.decode(&bytes);
+        let _ = decoder.decode(&bytes);
+        assert_eq!((decoder.hits(), decoder.misses()), (0, 2));
+        assert!(decoder.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Decoder extensible with custom data formats
+//!
+//! White-labeled sensors may reuse the Ruuvi manufacturer framing with a
+//! vendor specific format byte. [`Decoder`] dispatches such payloads to
+//! registered handlers and everything else to the built-in decoders.
+
+use std::collections::HashMap;
+
+use crate::{Result, RuuviData};
+
+/// Decodes one payload, format byte included, for a custom data format
+pub type FormatDecoder = fn(&[u8]) -> Result<RuuviData>;
+
+/// Decoder with custom handlers keyed by format byte
+///
+/// Handlers take precedence over the built-in formats, so a format byte can
+/// also be taken over entirely.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::decoder::Decoder;
+/// use ruuvi_decoders::{decode_bytes, RuuviData, Result};
+///
+/// // Vendor format 0x42 carries a Data Format 5 payload under its own byte
+/// fn vendor(payload: &[u8]) -> Result<RuuviData> {
+///     let mut v5 = payload.to_vec();
+///     v5[0] = 0x05;
+///     decode_bytes(&v5)
+/// }
+///
+/// let mut decoder = Decoder::new();
+/// decoder.register_format(0x42, vendor);
+///
+/// let bytes = hex::decode("4212FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert!(matches!(decoder.decode_bytes(&bytes), Ok(RuuviData::V5(_))));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Decoder {
+    custom: HashMap<u8, FormatDecoder>,
+}
+
+impl Decoder {
+    /// Create a decoder handling the built-in formats only
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle payloads starting with `format` with `decoder`
+    ///
+    /// # Returns
+    ///
+    /// The handler previously registered for `format`, if any
+    pub fn register_format(&mut self, format: u8, decoder: FormatDecoder) -> Option<FormatDecoder> {
+        self.custom.insert(format, decoder)
+    }
+
+    /// Remove the handler for `format`, restoring the built-in behavior
+    pub fn unregister_format(&mut self, format: u8) -> Option<FormatDecoder> {
+        self.custom.remove(&format)
+    }
+
+    /// Decode a BLE advertisement hex string, like [`crate::decode`]
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::decode`], or whatever a custom handler returns
+    pub fn decode(&self, ble_data: &str) -> Result<RuuviData> {
+        let bytes = crate::hex_to_bytes(&crate::clean_hex(ble_data)?)?;
+        self.decode_bytes(crate::ruuvi_payload(&bytes)?)
+    }
+
+    /// Decode a raw payload starting with the format byte, like [`crate::decode_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::decode_bytes`], or whatever a custom handler returns
+    pub fn decode_bytes(&self, bytes: &[u8]) -> Result<RuuviData> {
+        match bytes.first().and_then(|format| self.custom.get(format)) {
+            Some(decoder) => decoder(bytes),
+            None => crate::decode_bytes(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodeError;
+
+    const V5_BODY: &str = "12FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+
+    fn dummy(payload: &[u8]) -> Result<RuuviData> {
+        let mut v5 = payload.to_vec();
+        v5[0] = 0x05;
+        crate::decode_bytes(&v5)
+    }
+
+    fn reject(_: &[u8]) -> Result<RuuviData> {
+        Err(DecodeError::InvalidData("rejected".into()))
+    }
+
+    #[test]
+    fn test_custom_format() {
+        let mut decoder = Decoder::new();
+        let bytes = hex::decode(format!("42{V5_BODY}")).unwrap();
+        assert_eq!(
+            decoder.decode_bytes(&bytes),
+            Err(DecodeError::UnsupportedFormat(0x42))
+        );
+
+        assert!(decoder.register_format(0x42, dummy).is_none());
+        let expected = crate::decode_bytes(&hex::decode(format!("05{V5_BODY}")).unwrap());
+        assert_eq!(decoder.decode_bytes(&bytes), expected);
+        assert_eq!(
+            decoder.decode(&format!("0201061BFF990442{V5_BODY}")),
+            expected
+        );
+
+        assert!(decoder.unregister_format(0x42).is_some());
+        assert_eq!(
+            decoder.decode_bytes(&bytes),
+            Err(DecodeError::UnsupportedFormat(0x42))
+        );
+    }
+
+    #[test]
+    fn test_builtin_fallback_and_override() {
+        let mut decoder = Decoder::new();
+        decoder.register_format(0x42, dummy);
+
+        let v5 = hex::decode(format!("05{V5_BODY}")).unwrap();
+        assert_eq!(decoder.decode_bytes(&v5), crate::decode_bytes(&v5));
+        assert_eq!(decoder.decode_bytes(&[]), crate::decode_bytes(&[]));
+
+        decoder.register_format(0x05, reject);
+        assert_eq!(
+            decoder.decode_bytes(&v5),
+            Err(DecodeError::InvalidData("rejected".into()))
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Why each field of a reading is present or missing
+//!
+//! A decoded `None` can mean the format's "not available" sentinel was sent,
+//! typically a sensor that is not fitted, or that the raw value was beyond the
+//! range the format allows, which points at a transient sensor error.
+//! [`FieldStatus`] tells the two apart, see [`crate::decode_with_diagnostics`].
+
+use serde::Serialize;
+
+use crate::e1::DataFormatE1;
+use crate::ruuvi_data::RuuviData;
+use crate::v5::DataFormatV5;
+use crate::v6::DataFormatV6;
+use crate::validation::{
+    HUMIDITY_MAX, HUMIDITY_MIN, PRESSURE_MAX, PRESSURE_MIN, TEMPERATURE_MAX, TEMPERATURE_MIN,
+};
+
+/// Raw values marking a 9-bit VOC or `NOx` index as not available: 0 while
+/// the index algorithm initializes, 511 when the sensor is unavailable
+const GAS_INDEX_SENTINELS: [u16; 2] = [0, 0x1FF];
+
+/// State of one decoded field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum FieldState {
+    /// The field holds a plausible reading
+    Present,
+    /// The format's "not available" sentinel was sent
+    Invalid,
+    /// The raw value is beyond the format range, or the decoded reading is
+    /// outside the plausible range of [`crate::validation`]
+    OutOfRange,
+}
+
+/// State of every sensor field of a reading, in payload order
+///
+/// Field names match `readings()` on the format specific structs. The MAC
+/// address, flags and reserved bytes are not included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldStatus {
+    fields: Vec<(&'static str, FieldState)>,
+}
+
+impl FieldStatus {
+    /// Classify the fields of `data`, decoded from `payload`
+    ///
+    /// `payload` is indexed directly, so it must be the full payload `data`
+    /// was decoded from; only reached through [`crate::decode_with_diagnostics`].
+    #[must_use]
+    pub(crate) fn new(payload: &[u8], data: &RuuviData) -> Self {
+        let fields = match data {
+            RuuviData::V5(v5) => v5_fields(v5),
+            RuuviData::V6(v6) => v6_fields(payload, v6),
+            RuuviData::E1(e1) => e1_fields(payload, e1),
+        };
+        Self { fields }
+    }
+
+    /// State of the field called `name`, `None` if the format has no such field
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<FieldState> {
+        self.iter()
+            .find_map(|(field, state)| (field == name).then_some(state))
+    }
+
+    /// All fields as `(name, state)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, FieldState)> + '_ {
+        self.fields.iter().copied()
+    }
+
+    /// Names of the fields in `state`
+    pub fn fields_in(&self, state: FieldState) -> impl Iterator<Item = &'static str> + '_ {
+        self.iter()
+            .filter_map(move |(name, field_state)| (field_state == state).then_some(name))
+    }
+
+    /// Whether every field holds a plausible reading
+    #[must_use]
+    pub fn all_present(&self) -> bool {
+        self.iter().all(|(_, state)| state == FieldState::Present)
+    }
+}
+
+/// Classify a field whose only reason to be missing is its sentinel
+fn sentinel(present: bool) -> FieldState {
+    if present {
+        FieldState::Present
+    } else {
+        FieldState::Invalid
+    }
+}
+
+/// Classify a field that is also missing when its raw value is out of range
+fn ranged(present: bool, raw_is_sentinel: bool) -> FieldState {
+    if present || raw_is_sentinel {
+        sentinel(present)
+    } else {
+        FieldState::OutOfRange
+    }
+}
+
+/// Classify a reading that may decode fine but still be implausible
+fn plausible(value: Option<f64>, min: f64, max: f64) -> FieldState {
+    match value {
+        Some(value) if !(min..=max).contains(&value) => FieldState::OutOfRange,
+        value => sentinel(value.is_some()),
+    }
+}
+
+fn environment(
+    temperature: Option<f64>,
+    humidity: Option<f64>,
+    pressure: Option<f64>,
+) -> [(&'static str, FieldState); 3] {
+    [
+        (
+            "temperature",
+            plausible(temperature, TEMPERATURE_MIN, TEMPERATURE_MAX),
+        ),
+        ("humidity", plausible(humidity, HUMIDITY_MIN, HUMIDITY_MAX)),
+        ("pressure", plausible(pressure, PRESSURE_MIN, PRESSURE_MAX)),
+    ]
+}
+
+fn v5_fields(data: &DataFormatV5) -> Vec<(&'static str, FieldState)> {
+    let mut fields = environment(data.temperature, data.humidity, data.pressure).to_vec();
+    fields.extend([
+        ("acceleration_x", sentinel(data.acceleration_x.is_some())),
+        ("acceleration_y", sentinel(data.acceleration_y.is_some())),
+        ("acceleration_z", sentinel(data.acceleration_z.is_some())),
+        ("battery_voltage", sentinel(data.battery_voltage.is_some())),
+        ("tx_power", sentinel(data.tx_power.is_some())),
+        (
+            "movement_counter",
+            sentinel(data.movement_counter.is_some()),
+        ),
+        (
+            "measurement_sequence",
+            sentinel(data.measurement_sequence.is_some()),
+        ),
+    ]);
+    fields
+}
+
+fn v6_fields(payload: &[u8], data: &DataFormatV6) -> Vec<(&'static str, FieldState)> {
+    let u16_at = |start: usize| u16::from_be_bytes([payload[start], payload[start + 1]]);
+    let flags = payload[16];
+
+    let mut fields = environment(data.temperature, data.humidity, data.pressure).to_vec();
+    fields.extend([
+        ("pm2_5", ranged(data.pm2_5.is_some(), u16_at(7) == u16::MAX)),
+        ("co2", ranged(data.co2.is_some(), u16_at(9) == u16::MAX)),
+        (
+            "voc_index",
+            ranged(
+                data.voc_index.is_some(),
+                GAS_INDEX_SENTINELS.contains(&gas_index(payload[11], flags, 6)),
+            ),
+        ),
+        (
+            "nox_index",
+            ranged(
+                data.nox_index.is_some(),
+                GAS_INDEX_SENTINELS.contains(&gas_index(payload[12], flags, 7)),
+            ),
+        ),
+        ("luminosity", sentinel(data.luminosity.is_some())),
+        (
+            "measurement_sequence",
+            sentinel(data.measurement_sequence.is_some()),
+        ),
+    ]);
+    fields
+}
+
+fn e1_fields(payload: &[u8], data: &DataFormatE1) -> Vec<(&'static str, FieldState)> {
+    let flags = payload[28];
+
+    let mut fields = environment(data.temperature, data.humidity, data.pressure).to_vec();
+    fields.extend([
+        ("pm1_0", sentinel(data.pm1_0.is_some())),
+        ("pm2_5", sentinel(data.pm2_5.is_some())),
+        ("pm4_0", sentinel(data.pm4_0.is_some())),
+        ("pm10_0", sentinel(data.pm10_0.is_some())),
+        ("co2", sentinel(data.co2.is_some())),
+        (
+            "voc_index",
+            ranged(
+                data.voc_index.is_some(),
+                GAS_INDEX_SENTINELS.contains(&gas_index(payload[17], flags, 6)),
+            ),
+        ),
+        (
+            "nox_index",
+            ranged(
+                data.nox_index.is_some(),
+                GAS_INDEX_SENTINELS.contains(&gas_index(payload[18], flags, 7)),
+            ),
+        ),
+        ("luminosity", sentinel(data.luminosity.is_some())),
+        (
+            "measurement_sequence",
+            sentinel(data.measurement_sequence.is_some()),
+        ),
+    ]);
+    fields
+}
+
+/// Reassemble a 9-bit gas index from its high byte and the LSB in `flags`
+fn gas_index(high: u8, flags: u8, lsb_bit: u8) -> u16 {
+    (u16::from(high) << 1) | u16::from((flags >> lsb_bit) & 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn status(hex_str: &str) -> FieldStatus {
+        let payload = hex::decode(hex_str).unwrap();
+        FieldStatus::new(&payload, &RuuviData::decode(&payload).unwrap())
+    }
+
+    #[rstest]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    fn test_all_present(#[case] hex_str: &str) {
+        let status = status(hex_str);
+        assert!(status.all_present(), "{status:?}");
+        assert_eq!(status.get("temperature"), Some(FieldState::Present));
+        assert_eq!(status.get("mac_address"), None);
+    }
+
+    #[test]
+    fn test_v5_sentinels() {
+        let status = status("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF");
+        assert_eq!(status.iter().count(), 10);
+        assert_eq!(status.fields_in(FieldState::Invalid).count(), 10);
+    }
+
+    #[test]
+    fn test_v5_implausible() {
+        // Humidity 163.835% and pressure 1155.34 hPa decode, but are not plausible
+        let status = status("0512FCFFFEFFFE0004FFFC040CAC364200CDCBB8334C884F");
+        assert_eq!(
+            status.fields_in(FieldState::OutOfRange).collect::<Vec<_>>(),
+            ["humidity", "pressure"]
+        );
+    }
+
+    #[rstest]
+    #[case::pm2_5_sentinel("0612FC5394C37CFFFF", "pm2_5", FieldState::Invalid)]
+    #[case::pm2_5_over_range("0612FC5394C37C2711", "pm2_5", FieldState::OutOfRange)]
+    fn test_v6_pm2_5(#[case] prefix: &str, #[case] field: &str, #[case] expected: FieldState) {
+        let status = status(&format!("{prefix}00C90501D9FFCD004C884F"));
+        assert_eq!(status.get(field), Some(expected));
+    }
+
+    #[rstest]
+    #[case::co2_sentinel("FFFF0501", 0x00, "co2", FieldState::Invalid)]
+    #[case::co2_over_range("9C410501", 0x00, "co2", FieldState::OutOfRange)]
+    #[case::voc_sentinel("00C9FF01", 0x40, "voc_index", FieldState::Invalid)]
+    #[case::voc_initializing("00C90001", 0x00, "voc_index", FieldState::Invalid)]
+    #[case::voc_over_range("00C9FA01", 0x40, "voc_index", FieldState::OutOfRange)]
+    #[case::nox_sentinel("00C905FF", 0x80, "nox_index", FieldState::Invalid)]
+    #[case::nox_over_range("00C905FB", 0x00, "nox_index", FieldState::OutOfRange)]
+    fn test_v6_ranged(
+        #[case] co2_voc_nox: &str,
+        #[case] flags: u8,
+        #[case] field: &str,
+        #[case] expected: FieldState,
+    ) {
+        let status = status(&format!(
+            "06170C5668C79E0070{co2_voc_nox}D9FFCD{flags:02X}4C884F"
+        ));
+        assert_eq!(status.get(field), Some(expected));
+    }
+
+    #[test]
+    fn test_e1_gas_index() {
+        let status = status(
+            "E1170C5668C79E0065007004BD11CA00C9FFFB13E0AC000000DECDEED00000000000CBB8334C884F",
+        );
+        assert_eq!(status.get("voc_index"), Some(FieldState::Invalid));
+        assert_eq!(status.get("nox_index"), Some(FieldState::OutOfRange));
+    }
+}
@@ -1,4 +1,11 @@
+use crate::air_quality;
+use crate::encode;
 use crate::error::{DecodeError, Result};
+use crate::flags::Flags;
+use crate::mac::MacAddress;
+use crate::readings::{self, ReadingValue};
+use crate::sequence;
+use crate::units::{self, Luminosity};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
 
@@ -9,26 +16,31 @@ pub const PAYLOAD_WITH_MAC_AND_FLAGS_LENGTH: usize = PAYLOAD_WITH_MAC_LENGTH + F
 
 /// Data Format E1 (Extended v1) structure, as specified in the Ruuvi E1 XML spec.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
 pub struct DataFormatE1 {
     /// Temperature in Celsius (-163.835 to +163.835°C, 0.005°C resolution)
     pub temperature: Option<f64>,
     /// Humidity in % (0 to 100%, 0.0025% resolution, but up to 163.83% possible)
     pub humidity: Option<f64>,
-    /// Pressure in hPa (500.00 to 1155.34 hPa, 1 Pa resolution, offset -50000 Pa)
+    /// Pressure in hPa (500.00 to 1155.34 hPa, 1 Pa resolution, raw value + 50000 Pa)
     pub pressure: Option<f64>,
     /// PM1.0 in μg/m³ (0.1 μg/m³ resolution, 0..1000 μg/m³)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm1"))]
     pub pm1_0: Option<f64>,
     /// PM2.5 in μg/m³ (0.1 μg/m³ resolution, 0..1000 μg/m³)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm25"))]
     pub pm2_5: Option<f64>,
     /// PM4.0 in μg/m³ (0.1 μg/m³ resolution, 0..1000 μg/m³)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm4"))]
     pub pm4_0: Option<f64>,
     /// PM10.0 in μg/m³ (0.1 μg/m³ resolution, 0..1000 μg/m³)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm10"))]
     pub pm10_0: Option<f64>,
     /// CO2 concentration in ppm (1 ppm resolution, 0..40000 ppm)
     pub co2: Option<u16>,
-    /// VOC index, unitless (1/bit, 0..500, 9 bits: 8 from byte + 1 from flags)
+    /// VOC index, unitless (1/bit, 1..500, 9 bits: 8 from byte + 1 from flags)
     pub voc_index: Option<u16>,
-    /// `NOx` index, unitless (1/bit, 0..500, 9 bits: 8 from byte + 1 from flags)
+    /// `NOx` index, unitless (1/bit, 1..500, 9 bits: 8 from byte + 1 from flags)
     pub nox_index: Option<u16>,
     /// Luminosity, Lux (0.01 Lux/bit, 24 bits, 0..144284 Lux)
     pub luminosity: Option<f64>,
@@ -37,9 +49,233 @@ pub struct DataFormatE1 {
     /// Flags byte (bitfield, raw)
     pub flags: u8,
     /// MAC address as lowercase hex string (6 bytes, 48 bits)
+    #[cfg_attr(
+        feature = "mac-colons",
+        serde(
+            serialize_with = "crate::mac::serialize_colon_uppercase",
+            deserialize_with = "crate::mac::deserialize_bare"
+        )
+    )]
     pub mac_address: String,
 }
 
+/// Particulate matter readings of Data Format E1, in μg/m³
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
+pub struct ParticulateMatter {
+    /// PM1.0 in μg/m³
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm1"))]
+    pub pm1_0: Option<f64>,
+    /// PM2.5 in μg/m³
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm25"))]
+    pub pm2_5: Option<f64>,
+    /// PM4.0 in μg/m³
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm4"))]
+    pub pm4_0: Option<f64>,
+    /// PM10.0 in μg/m³
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm10"))]
+    pub pm10_0: Option<f64>,
+}
+
+impl DataFormatE1 {
+    /// The four particulate matter readings together
+    #[must_use]
+    pub fn particulate_matter(&self) -> ParticulateMatter {
+        ParticulateMatter {
+            pm1_0: self.pm1_0,
+            pm2_5: self.pm2_5,
+            pm4_0: self.pm4_0,
+            pm10_0: self.pm10_0,
+        }
+    }
+
+    /// US EPA Air Quality Index of the PM2.5 reading, `None` when PM2.5 is unavailable
+    ///
+    /// See [`air_quality::us_aqi_pm25`].
+    #[must_use]
+    pub fn us_aqi_pm25(&self) -> Option<u32> {
+        self.pm2_5.map(air_quality::us_aqi_pm25)
+    }
+
+    /// Humidity in %, clamped to 100%
+    ///
+    /// The sensor can report up to 163.83% RH; the `humidity` field keeps the
+    /// raw value while this accessor caps it for display.
+    #[must_use]
+    pub fn humidity_clamped(&self) -> Option<f64> {
+        self.humidity.map(|humidity| humidity.min(100.0))
+    }
+
+    /// Heat index in Celsius, see [`units::heat_index`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range the index is defined for.
+    #[must_use]
+    pub fn heat_index_c(&self) -> Option<f64> {
+        units::heat_index(self.temperature?, self.humidity?)
+    }
+
+    /// Humidex, see [`units::humidex`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range humidex is defined for.
+    #[must_use]
+    pub fn humidex(&self) -> Option<f64> {
+        units::humidex(self.temperature?, self.humidity?)
+    }
+
+    /// Copy with every float field snapped to its resolution
+    #[must_use]
+    pub fn rounded(&self) -> Self {
+        let pm = |value: Option<f64>| value.map(|pm| units::snap_to_resolution(pm, 10.0));
+        Self {
+            temperature: self
+                .temperature
+                .map(|t| units::snap_to_resolution(t, 200.0)),
+            humidity: self.humidity.map(|h| units::snap_to_resolution(h, 400.0)),
+            pressure: self.pressure.map(|p| units::snap_to_resolution(p, 100.0)),
+            pm1_0: pm(self.pm1_0),
+            pm2_5: pm(self.pm2_5),
+            pm4_0: pm(self.pm4_0),
+            pm10_0: pm(self.pm10_0),
+            luminosity: self
+                .luminosity
+                .map(|lux| units::snap_to_resolution(lux, 100.0)),
+            ..self.clone()
+        }
+    }
+
+    /// Pressure in Pa, rounded to the 1 Pa resolution of the format
+    #[must_use]
+    pub fn pressure_pa(&self) -> Option<f64> {
+        self.pressure.map(|hpa| units::hpa_to_pa(hpa).round())
+    }
+
+    /// Pressure in hPa, same as the `pressure` field
+    #[must_use]
+    pub fn pressure_hpa(&self) -> Option<f64> {
+        self.pressure
+    }
+
+    /// Pressure in mmHg
+    #[must_use]
+    pub fn pressure_mmhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_mmhg)
+    }
+
+    /// Pressure in inHg
+    #[must_use]
+    pub fn pressure_inhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_inhg)
+    }
+
+    /// Pressure reduced to mean sea level in hPa, for a station at `altitude_m` metres
+    ///
+    /// See [`units::sea_level_pressure`].
+    #[must_use]
+    pub fn pressure_at_sea_level(&self, altitude_m: f64) -> Option<f64> {
+        self.pressure
+            .map(|hpa| units::sea_level_pressure(hpa, altitude_m))
+    }
+
+    /// Check whether this reading was measured after `other`, handling counter wraparound
+    ///
+    /// Returns `None` when either measurement sequence is unavailable.
+    #[must_use]
+    pub fn is_newer_than(&self, other: &Self) -> Option<bool> {
+        let current = self.measurement_sequence?;
+        let other = other.measurement_sequence?;
+        Some(sequence::is_newer(
+            current,
+            other,
+            sequence::E1_SEQUENCE_MODULUS,
+        ))
+    }
+
+    /// Available fields as `(name, value)` pairs, skipping `None` fields
+    ///
+    /// Names match the struct fields.
+    pub fn readings(&self) -> impl Iterator<Item = (&'static str, ReadingValue)> {
+        readings::available([
+            ("temperature", self.temperature.map(ReadingValue::F64)),
+            ("humidity", self.humidity.map(ReadingValue::F64)),
+            ("pressure", self.pressure.map(ReadingValue::F64)),
+            ("pm1_0", self.pm1_0.map(ReadingValue::F64)),
+            ("pm2_5", self.pm2_5.map(ReadingValue::F64)),
+            ("pm4_0", self.pm4_0.map(ReadingValue::F64)),
+            ("pm10_0", self.pm10_0.map(ReadingValue::F64)),
+            ("co2", self.co2.map(ReadingValue::U16)),
+            ("voc_index", self.voc_index.map(ReadingValue::U16)),
+            ("nox_index", self.nox_index.map(ReadingValue::U16)),
+            ("luminosity", self.luminosity.map(ReadingValue::F64)),
+            (
+                "measurement_sequence",
+                self.measurement_sequence.map(ReadingValue::U32),
+            ),
+            ("flags", Some(ReadingValue::U8(self.flags))),
+            (
+                "mac_address",
+                Some(ReadingValue::String(self.mac_address.clone())),
+            ),
+        ])
+    }
+
+    /// Whether `other` reports the same physical conditions
+    ///
+    /// Compares the sensor fields only, ignoring `mac_address`,
+    /// `measurement_sequence` and `flags`.
+    #[must_use]
+    pub fn same_measurement(&self, other: &Self) -> bool {
+        self.temperature == other.temperature
+            && self.humidity == other.humidity
+            && self.pressure == other.pressure
+            && self.pm1_0 == other.pm1_0
+            && self.pm2_5 == other.pm2_5
+            && self.pm4_0 == other.pm4_0
+            && self.pm10_0 == other.pm10_0
+            && self.co2 == other.co2
+            && self.voc_index == other.voc_index
+            && self.nox_index == other.nox_index
+            && self.luminosity == other.luminosity
+    }
+
+    /// The MAC address as a [`MacAddress`]
+    ///
+    /// `None` if `mac_address` was set to something other than 12 hex digits.
+    #[must_use]
+    pub fn mac(&self) -> Option<MacAddress> {
+        self.mac_address.parse().ok()
+    }
+
+    /// Decode the status bits of the `flags` byte
+    #[must_use]
+    pub fn flags_decoded(&self) -> Flags {
+        Flags::from(self.flags)
+    }
+
+    /// CO2 level classification, `None` when CO2 is unavailable
+    #[must_use]
+    pub fn co2_level(&self) -> Option<air_quality::Co2Level> {
+        self.co2.map(air_quality::co2_level)
+    }
+
+    /// The higher of the raw VOC and `NOx` indexes
+    ///
+    /// The two indexes have different baselines, see
+    /// [`air_quality::combined_gas_index`].
+    #[must_use]
+    pub fn combined_gas_index(&self) -> Option<u16> {
+        air_quality::combined_gas_index(self.voc_index, self.nox_index)
+    }
+}
+
+impl Luminosity for DataFormatE1 {
+    /// Linear 24-bit value at 0.01 lx per bit
+    fn luminosity_lux(&self) -> Option<f64> {
+        self.luminosity
+    }
+}
+
 /// Decode Data Format E1 payload from raw bytes
 ///
 /// # Arguments
@@ -96,7 +332,7 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatE1> {
         Some(f64::from(raw_humidity) * 0.0025)
     };
 
-    // Pressure: 1 Pa/bit, offset -50000 Pa, u16, bytes 5-6
+    // Pressure: 1 Pa/bit, raw value + 50000 Pa, u16, bytes 5-6
     let raw_pressure = get_u16(5);
     let pressure = if raw_pressure == 65535 {
         None
@@ -148,18 +384,12 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatE1> {
     // VOC index: 9 bits, byte 17 (hi) + flags b6 (LSB, bit 6 of byte 28)
     let raw_voc_hi = u16::from(bytes[17]);
     let voc_flag = (u16::from(bytes[28]) & 0b0100_0000) >> 6;
-    let voc_index = {
-        let value = (raw_voc_hi << 1) | voc_flag;
-        if value > 500 { None } else { Some(value) }
-    };
+    let voc_index = air_quality::gas_index((raw_voc_hi << 1) | voc_flag);
 
     // NOx index: 9 bits, byte 18 (hi) + flags b7 (LSB, bit 7 of byte 28)
     let raw_nox_hi = u16::from(bytes[18]);
     let nox_flag = (u16::from(bytes[28]) & 0b1000_0000) >> 7;
-    let nox_index = {
-        let value = (raw_nox_hi << 1) | nox_flag;
-        if value > 500 { None } else { Some(value) }
-    };
+    let nox_index = air_quality::gas_index((raw_nox_hi << 1) | nox_flag);
 
     // Luminosity: 0.01 Lux/bit, u24, bytes 19-21
     let raw_lum = get_u32(19);
@@ -205,6 +435,91 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatE1> {
     })
 }
 
+/// Encode a reading back into a 40 byte Data Format E1 payload
+///
+/// This is the inverse of [`decode`]: `None` fields become their "not
+/// available" sentinels, reserved bytes are zero, and the low bits of VOC and
+/// `NOx` replace bits 6 and 7 of `flags`. Float fields are rounded to the
+/// resolution of the format.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::e1::{decode, encode};
+///
+/// let bytes = hex::decode(
+///     "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+/// )
+/// .unwrap();
+/// assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidTemperature`, `InvalidHumidity` or `InvalidPressure` -
+///   Value cannot be represented in the format
+/// * `DecodeError::InvalidData` - Air quality, luminosity or sequence value is out of range
+/// * `DecodeError::InvalidMacAddress` - `mac_address` is not 12 hex digits
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn encode(data: &DataFormatE1) -> Result<Vec<u8>> {
+    let (voc, nox, voc_nox_flags) = encode::pack_voc_nox(
+        encode::gas_index("VOC", data.voc_index)?,
+        encode::gas_index("NOx", data.nox_index)?,
+    );
+    let luminosity = match data.luminosity {
+        Some(lux) => encode::quantize(lux, 0.01, 0..=0x00FF_FFFE, |value| {
+            DecodeError::invalid_field("luminosity", &value)
+        })? as u32,
+        None => 0x00FF_FFFF,
+    };
+    let measurement_sequence = match data.measurement_sequence {
+        Some(sequence @ 0..=0x00FF_FFFE) => sequence,
+        Some(sequence) => {
+            return Err(DecodeError::invalid_field(
+                "measurement sequence",
+                &sequence.to_string(),
+            ));
+        }
+        None => 0x00FF_FFFF,
+    };
+    let flags = (data.flags & 0b0011_1111) | voc_nox_flags;
+
+    let mut bytes = Vec::with_capacity(PAYLOAD_WITH_MAC_LENGTH);
+    bytes.push(0xE1);
+    bytes.extend(encode::temperature(data.temperature)?);
+    bytes.extend(encode::humidity(data.humidity)?);
+    bytes.extend(encode::pressure(data.pressure)?);
+    for (name, pm) in [
+        ("PM1.0", data.pm1_0),
+        ("PM2.5", data.pm2_5),
+        ("PM4.0", data.pm4_0),
+        ("PM10.0", data.pm10_0),
+    ] {
+        bytes.extend(encode_pm(name, pm)?.to_be_bytes());
+    }
+    bytes.extend(data.co2.unwrap_or(u16::MAX).to_be_bytes());
+    bytes.push(voc);
+    bytes.push(nox);
+    bytes.extend(&luminosity.to_be_bytes()[1..]);
+    bytes.extend([0; 3]);
+    bytes.extend(&measurement_sequence.to_be_bytes()[1..]);
+    bytes.push(flags);
+    bytes.extend([0; 5]);
+    bytes.extend(encode::mac_address::<6>(&data.mac_address)?);
+    Ok(bytes)
+}
+
+/// Encode particulate matter in μg/m³ as 0.1 μg/m³ steps, `None` as `0xFFFF`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn encode_pm(name: &str, value: Option<f64>) -> Result<u16> {
+    match value {
+        Some(pm) => Ok(encode::quantize(pm, 0.1, 0..=65534, |value| {
+            DecodeError::invalid_field(name, &value)
+        })? as u16),
+        None => Ok(u16::MAX),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -221,6 +536,143 @@ mod tests {
         }
     }
 
+    // 0x1234 and 0x123456 read differently with their bytes swapped
+    #[rstest]
+    #[case::temperature(1, &[0x12, 0x34], |d: &DataFormatE1| d.temperature, 23.3)]
+    #[case::negative_temperature(1, &[0xFE, 0xDC], |d: &DataFormatE1| d.temperature, -1.46)]
+    #[case::humidity(3, &[0x12, 0x34], |d: &DataFormatE1| d.humidity, 11.65)]
+    #[case::pressure(5, &[0x12, 0x34], |d: &DataFormatE1| d.pressure, 546.6)]
+    #[case::pm1_0(7, &[0x12, 0x34], |d: &DataFormatE1| d.pm1_0, 466.0)]
+    #[case::pm2_5(9, &[0x12, 0x34], |d: &DataFormatE1| d.pm2_5, 466.0)]
+    #[case::pm4_0(11, &[0x12, 0x34], |d: &DataFormatE1| d.pm4_0, 466.0)]
+    #[case::pm10_0(13, &[0x12, 0x34], |d: &DataFormatE1| d.pm10_0, 466.0)]
+    #[case::co2(15, &[0x12, 0x34], |d: &DataFormatE1| d.co2.map(f64::from), 4660.0)]
+    #[case::luminosity(19, &[0x12, 0x34, 0x56], |d: &DataFormatE1| d.luminosity, 11_930.46)]
+    #[case::measurement_sequence(25, &[0x12, 0x34, 0x56], |d: &DataFormatE1| d.measurement_sequence.map(f64::from), 1_193_046.0)]
+    fn test_fields_are_big_endian(
+        #[case] offset: usize,
+        #[case] field_bytes: &[u8],
+        #[case] field: fn(&DataFormatE1) -> Option<f64>,
+        #[case] expected: f64,
+    ) {
+        let mut raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        raw[offset..offset + field_bytes.len()].copy_from_slice(field_bytes);
+        let value = field(&decode(&raw).unwrap()).unwrap();
+        assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_is_newer_than() {
+        let raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let older = decode(&raw).unwrap();
+        let newer = DataFormatE1 {
+            measurement_sequence: Some(older.measurement_sequence.unwrap() + 1),
+            ..older.clone()
+        };
+        let unknown = DataFormatE1 {
+            measurement_sequence: None,
+            ..older.clone()
+        };
+
+        assert_eq!(newer.is_newer_than(&older), Some(true));
+        assert_eq!(older.is_newer_than(&newer), Some(false));
+        assert_eq!(older.is_newer_than(&unknown), None);
+    }
+
+    #[test]
+    fn test_mac() {
+        let raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let mac = decode(&raw).unwrap().mac().unwrap();
+        assert_eq!(mac.to_string(), "cb:b8:33:4c:88:4f");
+        assert!(!mac.is_broadcast());
+    }
+
+    #[test]
+    fn test_flags_decoded() {
+        let raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        // 0x10 only has a reserved bit set
+        assert_eq!(decode(&raw).unwrap().flags_decoded(), Flags::default());
+
+        let raw = hex::decode(
+            "E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F",
+        )
+        .unwrap();
+        let flags = decode(&raw).unwrap().flags_decoded();
+        assert!(flags.calibration_in_progress);
+        assert!(flags.button_pressed);
+        assert!(flags.rtc_running);
+    }
+
+    #[rstest]
+    #[case("1388", Some(12.5), Some(12.5))]
+    #[case("9C40", Some(100.0), Some(100.0))]
+    #[case("FFFE", Some(163.835), Some(100.0))]
+    #[case("FFFF", None, None)]
+    fn test_humidity_clamped(
+        #[case] raw: &str,
+        #[case] humidity: Option<f64>,
+        #[case] clamped: Option<f64>,
+    ) {
+        let hex_str = format!(
+            "E1170C{raw}C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+        );
+        let data = decode(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(data.humidity, humidity);
+        assert_eq!(data.humidity_clamped(), clamped);
+    }
+
+    #[rstest]
+    #[case("005A", Some(50))] // 9.0 μg/m³
+    #[case("0078", Some(56))] // 12.0 μg/m³
+    #[case("0162", Some(100))] // 35.4 μg/m³
+    #[case("022A", Some(150))] // 55.4 μg/m³
+    #[case("FFFF", None)]
+    fn test_us_aqi_pm25(#[case] raw: &str, #[case] expected: Option<u32>) {
+        let hex_str = format!(
+            "E1170C5668C79E0065{raw}04BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+        );
+        let data = decode(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(data.us_aqi_pm25(), expected);
+    }
+
+    #[test]
+    fn test_particulate_matter() {
+        let raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let data = decode(&raw).unwrap();
+        let pm = data.particulate_matter();
+        assert_eq!(pm.pm1_0, data.pm1_0);
+        assert_eq!(pm.pm2_5, Some(11.200_000_000_000_001));
+        assert_eq!(pm.pm10_0, data.pm10_0);
+        assert_eq!(data.us_aqi_pm25(), Some(55));
+    }
+
+    #[rstest]
+    #[case("0000", Some(500.0))]
+    #[case("FFFE", Some(1155.34))]
+    #[case("FFFF", None)]
+    fn test_pressure_boundaries(#[case] raw: &str, #[case] expected: Option<f64>) {
+        let hex_str = format!(
+            "E1170C5668{raw}0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+        );
+        let data = decode(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(data.pressure, expected);
+    }
+
     #[test]
     fn test_decode_wrong_format() {
         let mut bytes: [u8; PAYLOAD_WITH_MAC_LENGTH] = [0; PAYLOAD_WITH_MAC_LENGTH];
@@ -253,4 +705,72 @@ mod tests {
         // Snapshot the whole decoded `DataFormatV5` for these canonical payloads.
         assert_debug_snapshot!(name, res);
     }
+
+    #[rstest]
+    #[case("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    #[case("E1800100000000000000000000000000000000000000000000000000C00000000000CBB8334C884F")]
+    #[case("E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F")]
+    fn test_encode_round_trip(#[case] hex_str: &str) {
+        let raw = hex::decode(hex_str).unwrap();
+        assert_eq!(encode(&decode(&raw).unwrap()).unwrap(), raw);
+    }
+
+    #[rstest]
+    #[case(0x05, 0x01, 0x00, Some(10), Some(2))]
+    #[case(0x05, 0x01, 0x40, Some(11), Some(2))] // bit 6 is the VOC LSB
+    #[case(0x05, 0x01, 0x80, Some(10), Some(3))] // bit 7 is the NOx LSB
+    #[case(0x00, 0x00, 0xC0, Some(1), Some(1))]
+    #[case(0x00, 0x00, 0xFF, Some(1), Some(1))] // other flag bits are ignored
+    #[case(0x00, 0x00, 0x3F, None, None)] // 0 means not available yet
+    #[case(0xFA, 0xFA, 0x00, Some(500), Some(500))]
+    #[case(0xFA, 0xFA, 0xC0, None, None)] // 501 is out of range
+    #[case(0xFF, 0xFF, 0xC0, None, None)]
+    fn test_voc_nox_reconstruction(
+        #[case] voc: u8,
+        #[case] nox: u8,
+        #[case] flags: u8,
+        #[case] voc_index: Option<u16>,
+        #[case] nox_index: Option<u16>,
+    ) {
+        let raw = hex::decode(format!(
+            "E1170C5668C79E0065007004BD11CA00C9{voc:02X}{nox:02X}13E0AC000000DECDEE{flags:02X}0000000000CBB8334C884F"
+        ))
+        .unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!((data.voc_index, data.nox_index), (voc_index, nox_index));
+
+        if let (Some(voc_index), Some(nox_index)) = (voc_index, nox_index) {
+            let (voc_hi, nox_hi, flag_bits) = encode::pack_voc_nox(voc_index, nox_index);
+            assert_eq!((voc_hi, nox_hi, flag_bits), (voc, nox, flags & 0xC0));
+        }
+    }
+
+    #[test]
+    fn test_encode_out_of_range() {
+        let raw = hex::decode(
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!(
+            encode(&DataFormatE1 {
+                pm2_5: Some(-0.5),
+                ..data.clone()
+            }),
+            Err(DecodeError::invalid_field(
+                "PM2.5",
+                "-0.5 is outside the encodable range"
+            ))
+        );
+        assert_eq!(
+            encode(&DataFormatE1 {
+                measurement_sequence: Some(0x0100_0000),
+                ..data
+            }),
+            Err(DecodeError::invalid_field(
+                "measurement sequence",
+                "16777216"
+            ))
+        );
+    }
 }
@@ -0,0 +1,167 @@
+//! Helpers shared by the per-format encoders
+//!
+//! See [`crate::v5::encode`], [`crate::v6::encode`] and [`crate::e1::encode`].
+
+use std::ops::RangeInclusive;
+
+use crate::air_quality::{GAS_INDEX_MAX, GAS_INDEX_MIN};
+use crate::error::{DecodeError, Result};
+
+/// Convert `value` to raw steps of `resolution`, failing outside `range`
+///
+/// `error` builds the field specific error, so a bad value is reported the
+/// same way as a bad field when decoding.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub(crate) fn quantize(
+    value: f64,
+    resolution: f64,
+    range: RangeInclusive<i64>,
+    error: impl FnOnce(String) -> DecodeError,
+) -> Result<i64> {
+    let raw = (value / resolution).round();
+
+    // Casting NaN saturates to 0, so check the float before the cast
+    if raw.is_nan() || raw < *range.start() as f64 || raw > *range.end() as f64 {
+        return Err(error(format!("{value} is outside the encodable range")));
+    }
+
+    Ok(raw as i64)
+}
+
+/// Encode temperature in Celsius as 0.005°C steps, `None` as `0x8000`
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn temperature(value: Option<f64>) -> Result<[u8; 2]> {
+    let raw = match value {
+        Some(celsius) => quantize(
+            celsius,
+            0.005,
+            -32767..=32767,
+            DecodeError::InvalidTemperature,
+        )? as i16,
+        None => i16::MIN,
+    };
+    Ok(raw.to_be_bytes())
+}
+
+/// Encode humidity in % as 0.0025% steps, `None` as `0xFFFF`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn humidity(value: Option<f64>) -> Result<[u8; 2]> {
+    let raw = match value {
+        Some(percent) => quantize(percent, 0.0025, 0..=65534, DecodeError::InvalidHumidity)? as u16,
+        None => u16::MAX,
+    };
+    Ok(raw.to_be_bytes())
+}
+
+/// Encode pressure in hPa as Pa above 50000 Pa, `None` as `0xFFFF`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn pressure(value: Option<f64>) -> Result<[u8; 2]> {
+    let raw = match value {
+        Some(hpa) => {
+            let pa = quantize(hpa, 0.01, 50000..=115_534, DecodeError::InvalidPressure)?;
+            (pa - 50000) as u16
+        }
+        None => u16::MAX,
+    };
+    Ok(raw.to_be_bytes())
+}
+
+/// Split 9 bit VOC and `NOx` indexes into their on-air parts
+///
+/// Data Formats 6 and E1 send the upper 8 bits of each index in its own byte
+/// and the lowest bits in the flags byte: VOC in bit 6, `NOx` in bit 7.
+/// Returns the VOC byte, the `NOx` byte and the bits to OR into the flags byte.
+/// Bits above the lowest 9 are dropped; 511 marks an unavailable index.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::encode::pack_voc_nox;
+///
+/// assert_eq!(pack_voc_nox(10, 2), (0x05, 0x01, 0x00));
+/// assert_eq!(pack_voc_nox(11, 3), (0x05, 0x01, 0b1100_0000));
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn pack_voc_nox(voc: u16, nox: u16) -> (u8, u8, u8) {
+    let flags = ((voc & 1) << 6) | ((nox & 1) << 7);
+    ((voc >> 1) as u8, (nox >> 1) as u8, flags as u8)
+}
+
+/// Check a VOC or `NOx` index fits the 1..=500 range, `None` as 511
+pub(crate) fn gas_index(name: &str, value: Option<u16>) -> Result<u16> {
+    match value {
+        Some(index @ GAS_INDEX_MIN..=GAS_INDEX_MAX) => Ok(index),
+        Some(index) => Err(DecodeError::invalid_field(name, &index.to_string())),
+        None => Ok(511),
+    }
+}
+
+/// Parse a lowercase or uppercase hex MAC address of `N` bytes
+pub(crate) fn mac_address<const N: usize>(mac_address: &str) -> Result<[u8; N]> {
+    hex::decode(mac_address)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| DecodeError::InvalidMacAddress(mac_address.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Some(24.3), Ok([0x12, 0xFC]))]
+    #[case(Some(-163.835), Ok([0x80, 0x01]))]
+    #[case(None, Ok([0x80, 0x00]))]
+    #[case(Some(-163.84), Err(DecodeError::InvalidTemperature("-163.84 is outside the encodable range".into())))]
+    #[case(Some(f64::NAN), Err(DecodeError::InvalidTemperature("NaN is outside the encodable range".into())))]
+    fn test_temperature(#[case] value: Option<f64>, #[case] expected: Result<[u8; 2]>) {
+        assert_eq!(temperature(value), expected);
+    }
+
+    #[rstest]
+    #[case(Some(1000.44), Ok([0xC3, 0x7C]))]
+    #[case(Some(500.0), Ok([0x00, 0x00]))]
+    #[case(None, Ok([0xFF, 0xFF]))]
+    #[case(Some(499.99), Err(DecodeError::InvalidPressure("499.99 is outside the encodable range".into())))]
+    fn test_pressure(#[case] value: Option<f64>, #[case] expected: Result<[u8; 2]>) {
+        assert_eq!(pressure(value), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0, (0x00, 0x00, 0b0000_0000))]
+    #[case(1, 0, (0x00, 0x00, 0b0100_0000))]
+    #[case(0, 1, (0x00, 0x00, 0b1000_0000))]
+    #[case(2, 2, (0x01, 0x01, 0b0000_0000))]
+    #[case(255, 256, (0x7F, 0x80, 0b0100_0000))]
+    #[case(500, 499, (0xFA, 0xF9, 0b1000_0000))]
+    #[case(511, 511, (0xFF, 0xFF, 0b1100_0000))]
+    fn test_pack_voc_nox(#[case] voc: u16, #[case] nox: u16, #[case] expected: (u8, u8, u8)) {
+        assert_eq!(pack_voc_nox(voc, nox), expected);
+    }
+
+    #[rstest]
+    #[case(None, Ok(511))]
+    #[case(Some(1), Ok(1))]
+    #[case(Some(500), Ok(500))]
+    #[case(Some(0), Err(DecodeError::invalid_field("VOC", "0")))]
+    #[case(Some(501), Err(DecodeError::invalid_field("VOC", "501")))]
+    fn test_gas_index(#[case] value: Option<u16>, #[case] expected: Result<u16>) {
+        assert_eq!(gas_index("VOC", value), expected);
+    }
+
+    #[test]
+    fn test_mac_address() {
+        assert_eq!(mac_address::<3>("4c884f"), Ok([0x4C, 0x88, 0x4F]));
+        assert_eq!(
+            mac_address::<6>("4c884f"),
+            Err(DecodeError::InvalidMacAddress("4c884f".into()))
+        );
+        assert_eq!(
+            mac_address::<3>("invalid"),
+            Err(DecodeError::InvalidMacAddress("invalid".into()))
+        );
+    }
+}
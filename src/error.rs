@@ -12,9 +12,13 @@ pub enum DecodeError {
     #[error("No data provided")]
     NoData,
 
-    /// Invalid hex string format
+    /// Invalid hex string format, with the precise cause as its source
     #[error("Invalid hex string: {0}")]
-    InvalidHex(String),
+    InvalidHex(#[from] hex::FromHexError),
+
+    /// Invalid base64 string format
+    #[error("Invalid base64 string: {0}")]
+    InvalidBase64(String),
 
     /// Data length is invalid for the format
     #[error("Invalid data length: {0}")]
@@ -32,7 +36,7 @@ pub enum DecodeError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
-    /// Decryption failed (for encrypted formats like E1)
+    /// Decryption failed (reserved for encrypted formats; E1 is plaintext)
     #[error("Decryption failed: {0}")]
     DecryptionFailed(String),
 
@@ -42,9 +46,73 @@ pub enum DecodeError {
 
     #[error("Missing manufacturer ID")]
     MissingManufacturerId,
+
+    /// Temperature field could not be decoded
+    #[error("Invalid temperature: {0}")]
+    InvalidTemperature(String),
+
+    /// Humidity field could not be decoded
+    #[error("Invalid humidity: {0}")]
+    InvalidHumidity(String),
+
+    /// Pressure field could not be decoded
+    #[error("Invalid pressure: {0}")]
+    InvalidPressure(String),
+
+    /// Acceleration field could not be decoded
+    #[error("Invalid acceleration: {0}")]
+    InvalidAcceleration(String),
+
+    /// Power info (battery voltage and TX power) field could not be decoded
+    #[error("Invalid power info: {0}")]
+    InvalidPowerInfo(String),
+
+    /// Movement counter field could not be decoded
+    #[error("Invalid movement counter: {0}")]
+    InvalidMovementCounter(String),
+
+    /// Measurement sequence field could not be decoded
+    #[error("Invalid measurement sequence: {0}")]
+    InvalidMeasurementSequence(String),
+
+    /// MAC address bytes are missing or all `0xFF`
+    #[error("Invalid MAC address: {0}")]
+    InvalidMacAddress(String),
+
+    /// Reading the input failed
+    #[error("I/O error: {0}")]
+    Io(String),
 }
 
 impl DecodeError {
+    /// Name of the error variant, without any payload
+    ///
+    /// Useful as a stable key when counting errors by kind.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NoData => "NoData",
+            Self::InvalidHex(_) => "InvalidHex",
+            Self::InvalidBase64(_) => "InvalidBase64",
+            Self::InvalidLength(_) => "InvalidLength",
+            Self::UnsupportedFormat(_) => "UnsupportedFormat",
+            Self::InvalidData(_) => "InvalidData",
+            Self::ValidationFailed(_) => "ValidationFailed",
+            Self::DecryptionFailed(_) => "DecryptionFailed",
+            Self::MissingField(_) => "MissingField",
+            Self::MissingManufacturerId => "MissingManufacturerId",
+            Self::InvalidTemperature(_) => "InvalidTemperature",
+            Self::InvalidHumidity(_) => "InvalidHumidity",
+            Self::InvalidPressure(_) => "InvalidPressure",
+            Self::InvalidAcceleration(_) => "InvalidAcceleration",
+            Self::InvalidPowerInfo(_) => "InvalidPowerInfo",
+            Self::InvalidMovementCounter(_) => "InvalidMovementCounter",
+            Self::InvalidMeasurementSequence(_) => "InvalidMeasurementSequence",
+            Self::InvalidMacAddress(_) => "InvalidMacAddress",
+            Self::Io(_) => "Io",
+        }
+    }
+
     /// Create a new `InvalidLength` error
     #[must_use]
     pub fn invalid_length(expected: usize, actual: usize) -> Self {
@@ -62,6 +130,18 @@ impl DecodeError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_invalid_hex_source() {
+        use std::error::Error;
+
+        let err = DecodeError::from(hex::FromHexError::OddLength);
+        assert_eq!(err.to_string(), "Invalid hex string: Odd number of digits");
+        assert_eq!(
+            err.source().unwrap().downcast_ref::<hex::FromHexError>(),
+            Some(&hex::FromHexError::OddLength)
+        );
+    }
+
     #[test]
     fn test_error_display() {
         let err = DecodeError::UnsupportedFormat(99);
@@ -78,6 +158,12 @@ mod tests {
             err.to_string(),
             "Invalid data values: Invalid temperature value: -163.84"
         );
+
+        let err = DecodeError::InvalidHumidity("bytes 3..5 must be 2 bytes, got 1".into());
+        assert_eq!(
+            err.to_string(),
+            "Invalid humidity: bytes 3..5 must be 2 bytes, got 1"
+        );
     }
 
     #[test]
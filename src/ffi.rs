@@ -0,0 +1,208 @@
+//! C FFI layer, enabled with the `cffi` feature
+//!
+//! Build a static library for linking into C/C++ firmware with
+//! `cargo rustc --release --features cffi --crate-type staticlib`.
+//!
+//! Functions return `RUUVI_OK` (0) on success or one of the negative
+//! `RUUVI_ERR_*` codes, one per `DecodeError` variant. Absent readings are
+//! reported as NaN in the output struct.
+
+use crate::error::DecodeError;
+use crate::v5;
+
+/// Decoding succeeded
+pub const RUUVI_OK: i32 = 0;
+/// `DecodeError::NoData`
+pub const RUUVI_ERR_NO_DATA: i32 = -1;
+/// `DecodeError::InvalidHex`
+pub const RUUVI_ERR_INVALID_HEX: i32 = -2;
+/// `DecodeError::InvalidLength`
+pub const RUUVI_ERR_INVALID_LENGTH: i32 = -3;
+/// `DecodeError::UnsupportedFormat`
+pub const RUUVI_ERR_UNSUPPORTED_FORMAT: i32 = -4;
+/// `DecodeError::InvalidData`
+pub const RUUVI_ERR_INVALID_DATA: i32 = -5;
+/// `DecodeError::ValidationFailed`
+pub const RUUVI_ERR_VALIDATION_FAILED: i32 = -6;
+/// `DecodeError::DecryptionFailed`
+pub const RUUVI_ERR_DECRYPTION_FAILED: i32 = -7;
+/// `DecodeError::MissingField`
+pub const RUUVI_ERR_MISSING_FIELD: i32 = -8;
+/// `DecodeError::MissingManufacturerId`
+pub const RUUVI_ERR_MISSING_MANUFACTURER_ID: i32 = -9;
+/// `DecodeError::InvalidBase64`
+pub const RUUVI_ERR_INVALID_BASE64: i32 = -10;
+/// `DecodeError::InvalidMacAddress`
+pub const RUUVI_ERR_INVALID_MAC_ADDRESS: i32 = -11;
+/// `DecodeError::InvalidTemperature`
+pub const RUUVI_ERR_INVALID_TEMPERATURE: i32 = -12;
+/// `DecodeError::InvalidHumidity`
+pub const RUUVI_ERR_INVALID_HUMIDITY: i32 = -13;
+/// `DecodeError::InvalidPressure`
+pub const RUUVI_ERR_INVALID_PRESSURE: i32 = -14;
+/// `DecodeError::InvalidAcceleration`
+pub const RUUVI_ERR_INVALID_ACCELERATION: i32 = -15;
+/// `DecodeError::InvalidPowerInfo`
+pub const RUUVI_ERR_INVALID_POWER_INFO: i32 = -16;
+/// `DecodeError::InvalidMovementCounter`
+pub const RUUVI_ERR_INVALID_MOVEMENT_COUNTER: i32 = -17;
+/// `DecodeError::InvalidMeasurementSequence`
+pub const RUUVI_ERR_INVALID_MEASUREMENT_SEQUENCE: i32 = -18;
+/// `DecodeError::Io`
+pub const RUUVI_ERR_IO: i32 = -19;
+/// A required pointer argument was null
+pub const RUUVI_ERR_NULL_POINTER: i32 = -100;
+
+/// Data Format 5 reading with C layout
+///
+/// Every reading is a `double`, NaN when the sensor did not provide it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CV5 {
+    /// Temperature in Celsius
+    pub temperature: f64,
+    /// Humidity in %
+    pub humidity: f64,
+    /// Pressure in hPa
+    pub pressure: f64,
+    /// Acceleration X-axis in mg
+    pub acceleration_x: f64,
+    /// Acceleration Y-axis in mg
+    pub acceleration_y: f64,
+    /// Acceleration Z-axis in mg
+    pub acceleration_z: f64,
+    /// Battery voltage in mV
+    pub battery_voltage: f64,
+    /// TX power in dBm
+    pub tx_power: f64,
+    /// Movement counter
+    pub movement_counter: f64,
+    /// Measurement sequence number
+    pub measurement_sequence: f64,
+    /// MAC address bytes as transmitted (all 0xFF when invalid)
+    pub mac_address: [u8; 6],
+}
+
+/// Map a `DecodeError` to its `RUUVI_ERR_*` code
+#[must_use]
+pub fn error_code(err: &DecodeError) -> i32 {
+    match err {
+        DecodeError::NoData => RUUVI_ERR_NO_DATA,
+        DecodeError::InvalidHex(_) => RUUVI_ERR_INVALID_HEX,
+        DecodeError::InvalidBase64(_) => RUUVI_ERR_INVALID_BASE64,
+        DecodeError::InvalidLength(_) => RUUVI_ERR_INVALID_LENGTH,
+        DecodeError::UnsupportedFormat(_) => RUUVI_ERR_UNSUPPORTED_FORMAT,
+        DecodeError::InvalidData(_) => RUUVI_ERR_INVALID_DATA,
+        DecodeError::ValidationFailed(_) => RUUVI_ERR_VALIDATION_FAILED,
+        DecodeError::DecryptionFailed(_) => RUUVI_ERR_DECRYPTION_FAILED,
+        DecodeError::MissingField(_) => RUUVI_ERR_MISSING_FIELD,
+        DecodeError::MissingManufacturerId => RUUVI_ERR_MISSING_MANUFACTURER_ID,
+        DecodeError::InvalidMacAddress(_) => RUUVI_ERR_INVALID_MAC_ADDRESS,
+        DecodeError::InvalidTemperature(_) => RUUVI_ERR_INVALID_TEMPERATURE,
+        DecodeError::InvalidHumidity(_) => RUUVI_ERR_INVALID_HUMIDITY,
+        DecodeError::InvalidPressure(_) => RUUVI_ERR_INVALID_PRESSURE,
+        DecodeError::InvalidAcceleration(_) => RUUVI_ERR_INVALID_ACCELERATION,
+        DecodeError::InvalidPowerInfo(_) => RUUVI_ERR_INVALID_POWER_INFO,
+        DecodeError::InvalidMovementCounter(_) => RUUVI_ERR_INVALID_MOVEMENT_COUNTER,
+        DecodeError::InvalidMeasurementSequence(_) => RUUVI_ERR_INVALID_MEASUREMENT_SEQUENCE,
+        DecodeError::Io(_) => RUUVI_ERR_IO,
+    }
+}
+
+/// Decode a Data Format 5 payload (format byte included, 24 bytes) into `out`
+///
+/// `out` is only written on success.
+///
+/// # Safety
+///
+/// * `bytes` must be valid for reads of `len` bytes
+/// * `out` must be valid for writes of one `CV5`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ruuvi_decode_v5(bytes: *const u8, len: usize, out: *mut CV5) -> i32 {
+    if bytes.is_null() || out.is_null() {
+        return RUUVI_ERR_NULL_POINTER;
+    }
+
+    // SAFETY: non-null and valid for `len` bytes per the caller contract
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, len) };
+    let data = match v5::decode(bytes) {
+        Ok(data) => data,
+        Err(err) => return error_code(&err),
+    };
+
+    let mut mac_address = [0u8; 6];
+    mac_address.copy_from_slice(&bytes[v5::PAYLOAD_LENGTH..v5::PAYLOAD_WITH_MAC_LENGTH]);
+
+    let result = CV5 {
+        temperature: data.temperature.unwrap_or(f64::NAN),
+        humidity: data.humidity.unwrap_or(f64::NAN),
+        pressure: data.pressure.unwrap_or(f64::NAN),
+        acceleration_x: data.acceleration_x.map_or(f64::NAN, f64::from),
+        acceleration_y: data.acceleration_y.map_or(f64::NAN, f64::from),
+        acceleration_z: data.acceleration_z.map_or(f64::NAN, f64::from),
+        battery_voltage: data.battery_voltage.map_or(f64::NAN, f64::from),
+        tx_power: data.tx_power.map_or(f64::NAN, f64::from),
+        movement_counter: data.movement_counter.map_or(f64::NAN, f64::from),
+        measurement_sequence: data.measurement_sequence.map_or(f64::NAN, f64::from),
+        mac_address,
+    };
+
+    // SAFETY: non-null and valid for writes per the caller contract
+    unsafe { out.write(result) };
+
+    RUUVI_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_ffi(bytes: &[u8]) -> (i32, Option<CV5>) {
+        let mut out = std::mem::MaybeUninit::<CV5>::uninit();
+        let code = unsafe { ruuvi_decode_v5(bytes.as_ptr(), bytes.len(), out.as_mut_ptr()) };
+        let out = (code == RUUVI_OK).then(|| unsafe { out.assume_init() });
+        (code, out)
+    }
+
+    #[test]
+    fn decode_valid() {
+        let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let (code, out) = decode_ffi(&bytes);
+        let out = out.unwrap();
+
+        assert_eq!(code, RUUVI_OK);
+        assert!((out.temperature - 24.3).abs() < 1e-9);
+        assert!((out.pressure - 1000.44).abs() < 1e-9);
+        assert!((out.acceleration_y - -4.0).abs() < 1e-9);
+        assert!((out.tx_power - 4.0).abs() < 1e-9);
+        assert_eq!(out.mac_address, [0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F]);
+    }
+
+    #[test]
+    fn decode_invalid_readings_are_nan() {
+        let bytes = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        let (code, out) = decode_ffi(&bytes);
+        let out = out.unwrap();
+
+        assert_eq!(code, RUUVI_OK);
+        assert!(out.temperature.is_nan());
+        assert!(out.humidity.is_nan());
+        assert!(out.battery_voltage.is_nan());
+        assert!(out.measurement_sequence.is_nan());
+        assert_eq!(out.mac_address, [0xFF; 6]);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(decode_ffi(&[0x05, 0x12]).0, RUUVI_ERR_INVALID_LENGTH);
+        assert_eq!(decode_ffi(&[0x06; 24]).0, RUUVI_ERR_UNSUPPORTED_FORMAT);
+
+        let mut out = std::mem::MaybeUninit::<CV5>::uninit();
+        let code = unsafe { ruuvi_decode_v5(std::ptr::null(), 24, out.as_mut_ptr()) };
+        assert_eq!(code, RUUVI_ERR_NULL_POINTER);
+
+        let bytes = [0x05; 24];
+        let code = unsafe { ruuvi_decode_v5(bytes.as_ptr(), 24, std::ptr::null_mut()) };
+        assert_eq!(code, RUUVI_ERR_NULL_POINTER);
+    }
+}
@@ -0,0 +1,64 @@
+//! Status flags shared by Data Formats 6 and E1
+//!
+//! Bit layout of the flags byte:
+//!
+//! | Bit | Meaning                           |
+//! | --- | --------------------------------- |
+//! | 0   | Calibration in progress           |
+//! | 1   | Button pressed                    |
+//! | 2   | RTC running                       |
+//! | 3-5 | Reserved                          |
+//! | 6   | VOC index LSB (see `voc_index`)   |
+//! | 7   | `NOx` index LSB (see `nox_index`) |
+//!
+//! Bits 6 and 7 are already folded into the 9-bit gas indexes by the decoders
+//! and are not repeated here.
+
+use serde::{Deserialize, Serialize};
+
+const CALIBRATION_IN_PROGRESS: u8 = 0b0000_0001;
+const BUTTON_PRESSED: u8 = 0b0000_0010;
+const RTC_RUNNING: u8 = 0b0000_0100;
+
+/// Decoded status bits of the V6/E1 flags byte
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Flags {
+    /// Sensor calibration is in progress, readings may be inaccurate (bit 0)
+    pub calibration_in_progress: bool,
+    /// Button was pressed (bit 1)
+    pub button_pressed: bool,
+    /// Real time clock is running (bit 2)
+    pub rtc_running: bool,
+}
+
+impl From<u8> for Flags {
+    fn from(flags: u8) -> Self {
+        Self {
+            calibration_in_progress: flags & CALIBRATION_IN_PROGRESS != 0,
+            button_pressed: flags & BUTTON_PRESSED != 0,
+            rtc_running: flags & RTC_RUNNING != 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0b0000_0000, Flags::default())]
+    #[case(0b0000_0001, Flags { calibration_in_progress: true, ..Flags::default() })]
+    #[case(0b0000_0010, Flags { button_pressed: true, ..Flags::default() })]
+    #[case(0b0000_0100, Flags { rtc_running: true, ..Flags::default() })]
+    #[case(0b0000_1000, Flags::default())] // reserved
+    #[case(0b0001_0000, Flags::default())] // reserved
+    #[case(0b0010_0000, Flags::default())] // reserved
+    #[case(0b0100_0000, Flags::default())] // VOC LSB
+    #[case(0b1000_0000, Flags::default())] // NOx LSB
+    #[case(0b1111_1111, Flags { calibration_in_progress: true, button_pressed: true, rtc_running: true })]
+    fn flag_bits(#[case] byte: u8, #[case] expected: Flags) {
+        assert_eq!(Flags::from(byte), expected);
+    }
+}
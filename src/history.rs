@@ -0,0 +1,221 @@
+//! Ruuvi history log records read over GATT
+//!
+//! Ruuvi sensor firmware returns logged measurements over the Nordic UART Service
+//! as 11 byte records, one sensor value per record:
+//!
+//! | Bytes | Meaning                                              |
+//! | ----- | ---------------------------------------------------- |
+//! | 0     | Destination endpoint, `0x3A` (environmental)         |
+//! | 1     | Source endpoint: `0x30` temp, `0x31` hum, `0x32` pres |
+//! | 2     | Operation, `0x10` (log value write)                  |
+//! | 3-6   | Timestamp, seconds since epoch, u32 big-endian        |
+//! | 7-10  | Value, i32 big-endian                                |
+//!
+//! The log ends with a record whose source endpoint is `0x3A` and whose
+//! timestamp and value bytes are all `0xFF`.
+
+use crate::error::{DecodeError, Result};
+use crate::units;
+use serde::{Deserialize, Serialize};
+
+/// Length of one log record in bytes
+pub const LOG_RECORD_LENGTH: usize = 11;
+
+const ENDPOINT_ENVIRONMENTAL: u8 = 0x3A;
+const ENDPOINT_TEMPERATURE: u8 = 0x30;
+const ENDPOINT_HUMIDITY: u8 = 0x31;
+const ENDPOINT_PRESSURE: u8 = 0x32;
+const OP_LOG_VALUE_WRITE: u8 = 0x10;
+
+/// A single logged measurement
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LogMeasurement {
+    /// Temperature in Celsius (0.01°C resolution)
+    Temperature(f64),
+    /// Humidity in % (0.01% resolution)
+    Humidity(f64),
+    /// Pressure in hPa (1 Pa resolution)
+    Pressure(f64),
+}
+
+/// Timestamped measurement from the history log
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// Seconds since epoch
+    pub timestamp: u32,
+    /// Logged value
+    pub measurement: LogMeasurement,
+}
+
+/// Check whether `bytes` is the end of log marker record
+#[must_use]
+pub fn is_end_marker(bytes: &[u8]) -> bool {
+    bytes.len() == LOG_RECORD_LENGTH
+        && bytes[..3]
+            == [
+                ENDPOINT_ENVIRONMENTAL,
+                ENDPOINT_ENVIRONMENTAL,
+                OP_LOG_VALUE_WRITE,
+            ]
+        && bytes[3..].iter().all(|&b| b == 0xFF)
+}
+
+/// Decode one 11 byte history log record
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::history::{parse_log_record, LogMeasurement};
+///
+/// let bytes = hex::decode("3A30106710C9E0000009C4").unwrap();
+/// let record = parse_log_record(&bytes).unwrap();
+/// assert_eq!(record.timestamp, 1_729_153_504);
+/// assert_eq!(record.measurement, LogMeasurement::Temperature(25.0));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - Record is not 11 bytes
+/// * `DecodeError::InvalidData` - Unknown endpoint or operation, or the end of log marker
+pub fn parse_log_record(bytes: &[u8]) -> Result<LogRecord> {
+    if bytes.len() != LOG_RECORD_LENGTH {
+        return Err(DecodeError::invalid_length(LOG_RECORD_LENGTH, bytes.len()));
+    }
+    if is_end_marker(bytes) {
+        return Err(DecodeError::InvalidData("End of log marker".into()));
+    }
+    if bytes[0] != ENDPOINT_ENVIRONMENTAL || bytes[2] != OP_LOG_VALUE_WRITE {
+        return Err(DecodeError::invalid_field(
+            "log header",
+            &hex::encode_upper(&bytes[..3]),
+        ));
+    }
+
+    let timestamp = u32::from_be_bytes([bytes[3], bytes[4], bytes[5], bytes[6]]);
+    let value = f64::from(i32::from_be_bytes([
+        bytes[7], bytes[8], bytes[9], bytes[10],
+    ]));
+
+    let measurement = match bytes[1] {
+        ENDPOINT_TEMPERATURE => LogMeasurement::Temperature(value / 100.0),
+        ENDPOINT_HUMIDITY => LogMeasurement::Humidity(value / 100.0),
+        ENDPOINT_PRESSURE => LogMeasurement::Pressure(value / units::PA_PER_HPA),
+        other => {
+            return Err(DecodeError::invalid_field(
+                "log source endpoint",
+                &format!("0x{other:02X}"),
+            ));
+        }
+    };
+
+    Ok(LogRecord {
+        timestamp,
+        measurement,
+    })
+}
+
+/// Decode concatenated history log records, stopping at the end of log marker
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - Stream is not a whole number of records
+/// * Any error returned by [`parse_log_record`]
+pub fn parse_log_stream(bytes: &[u8]) -> Result<Vec<LogRecord>> {
+    if !bytes.len().is_multiple_of(LOG_RECORD_LENGTH) {
+        return Err(DecodeError::InvalidLength(format!(
+            "Log stream of {} bytes is not a multiple of {LOG_RECORD_LENGTH}",
+            bytes.len()
+        )));
+    }
+
+    bytes
+        .chunks_exact(LOG_RECORD_LENGTH)
+        .take_while(|record| !is_end_marker(record))
+        .map(parse_log_record)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const END_MARKER: &str = "3A3A10FFFFFFFFFFFFFFFF";
+
+    #[rstest]
+    #[case("3A30106710C9E0000009C4", LogMeasurement::Temperature(25.0))]
+    #[case("3A30106710C9E0FFFFFC18", LogMeasurement::Temperature(-10.0))]
+    #[case("3A31106710C9E000001483", LogMeasurement::Humidity(52.51))]
+    #[case("3A32106710C9E0000186A4", LogMeasurement::Pressure(1000.04))]
+    fn test_parse_log_record(#[case] hex_str: &str, #[case] expected: LogMeasurement) {
+        let record = parse_log_record(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(record.timestamp, 0x6710_C9E0);
+        assert_eq!(record.measurement, expected);
+    }
+
+    #[rstest]
+    #[case("3A30106710C9E0000009", DecodeError::invalid_length(11, 10))]
+    #[case(END_MARKER, DecodeError::InvalidData("End of log marker".into()))]
+    #[case(
+        "3A33106710C9E0000009C4",
+        DecodeError::invalid_field("log source endpoint", "0x33")
+    )]
+    #[case(
+        "3B30106710C9E0000009C4",
+        DecodeError::invalid_field("log header", "3B3010")
+    )]
+    fn test_parse_log_record_errors(#[case] hex_str: &str, #[case] expected: DecodeError) {
+        assert_eq!(
+            parse_log_record(&hex::decode(hex_str).unwrap()),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_log_stream() {
+        let stream = hex::decode(concat!(
+            "3A30106710C9E0000009C4",
+            "3A31106710C9E000001483",
+            "3A32106710C9E0000186A4",
+            "3A30106710CD64000009BA",
+            "3A3A10FFFFFFFFFFFFFFFF",
+        ))
+        .unwrap();
+        let records = parse_log_stream(&stream).unwrap();
+
+        assert_eq!(
+            records,
+            vec![
+                LogRecord {
+                    timestamp: 1_729_153_504,
+                    measurement: LogMeasurement::Temperature(25.0),
+                },
+                LogRecord {
+                    timestamp: 1_729_153_504,
+                    measurement: LogMeasurement::Humidity(52.51),
+                },
+                LogRecord {
+                    timestamp: 1_729_153_504,
+                    measurement: LogMeasurement::Pressure(1000.04),
+                },
+                LogRecord {
+                    timestamp: 1_729_154_404,
+                    measurement: LogMeasurement::Temperature(24.9),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_log_stream_errors() {
+        assert_eq!(parse_log_stream(&[]), Ok(vec![]));
+        assert!(matches!(
+            parse_log_stream(&[0x3A; 12]),
+            Err(DecodeError::InvalidLength(_))
+        ));
+        // Records after the end marker are ignored
+        let stream =
+            hex::decode(concat!("3A3A10FFFFFFFFFFFFFFFF", "3A33106710C9E0000009C4")).unwrap();
+        assert_eq!(parse_log_stream(&stream), Ok(vec![]));
+    }
+}
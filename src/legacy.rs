@@ -0,0 +1,206 @@
+//! Legacy Eddystone-URL formats 2 and 4
+//!
+//! Early `RuuviTag` firmware broadcast its readings as a URL such as
+//! `https://ruu.vi/#AjwYAMFc`, the fragment holding 6 bytes in URL-safe
+//! base64:
+//!
+//! | Byte | Meaning                                                   |
+//! | ---- | --------------------------------------------------------- |
+//! | 0    | Format, always 2                                          |
+//! | 1    | Humidity, 0.5%/bit                                        |
+//! | 2    | Temperature, whole °C, bit 7 is the sign                  |
+//! | 3    | Temperature, hundredths of °C                             |
+//! | 4-5  | Pressure, 1 Pa/bit, offset -50000 Pa, big-endian          |
+//!
+//! Format 4 appends one more base64 character to the fragment, a random tag
+//! identifier that does not fill a whole byte.
+
+use base64::Engine;
+
+use crate::error::{DecodeError, Result};
+use crate::ruuvi_data::RuuviData;
+use crate::v5::{DataFormatV5, UNKNOWN_MAC_ADDRESS};
+
+/// Base64 characters holding the 6 data bytes
+const DATA_LENGTH: usize = 8;
+/// Format byte inside the payload, shared by formats 2 and 4
+const PAYLOAD_FORMAT: u8 = 2;
+
+/// Readings of a format 2 or 4 URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataFormatLegacy {
+    /// Data format, 2 or 4 (decided by the fragment length)
+    pub format: u8,
+    /// Temperature in Celsius (-127.99 to +127.99°C, 0.01°C resolution)
+    pub temperature: f64,
+    /// Humidity in % (0 to 127.5%, 0.5% resolution)
+    pub humidity: f64,
+    /// Pressure in hPa (500.00 to 1155.35 hPa, 1 Pa resolution)
+    pub pressure: f64,
+    /// Tag identifier character of format 4, `None` for format 2
+    pub identifier: Option<char>,
+}
+
+impl From<DataFormatLegacy> for DataFormatV5 {
+    /// Carry the readings over; every other field is `None` and the MAC
+    /// address is [`UNKNOWN_MAC_ADDRESS`], as the URL has neither.
+    fn from(legacy: DataFormatLegacy) -> Self {
+        Self {
+            mac_address: UNKNOWN_MAC_ADDRESS.to_string(),
+            ..DataFormatV5::builder()
+                .temperature(legacy.temperature)
+                .humidity(legacy.humidity)
+                .pressure(legacy.pressure)
+                .build()
+        }
+    }
+}
+
+/// Decode the base64 fragment of a format 2 or 4 URL
+///
+/// # Arguments
+///
+/// * `fragment` - The part after `#`, 8 characters for format 2 or 9 for format 4
+///
+/// # Errors
+///
+/// * `DecodeError::NoData` - Empty fragment
+/// * `DecodeError::InvalidLength` - Fragment is not 8 or 9 characters long
+/// * `DecodeError::InvalidBase64` - Fragment is not URL-safe base64
+/// * `DecodeError::UnsupportedFormat` - Payload format byte is not 2
+pub fn decode(fragment: &str) -> Result<DataFormatLegacy> {
+    let fragment = fragment.trim();
+    if fragment.is_empty() {
+        return Err(DecodeError::NoData);
+    }
+
+    let (data, identifier) = match fragment.len() {
+        DATA_LENGTH => (fragment, None),
+        len if len == DATA_LENGTH + 1 && fragment.is_char_boundary(DATA_LENGTH) => {
+            let (data, identifier) = fragment.split_at(DATA_LENGTH);
+            (data, identifier.chars().next())
+        }
+        len => {
+            return Err(DecodeError::InvalidLength(format!(
+                "Expected {DATA_LENGTH} or {} base64 characters, got {len}",
+                DATA_LENGTH + 1
+            )));
+        }
+    };
+
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(|err| DecodeError::InvalidBase64(err.to_string()))?;
+    if bytes[0] != PAYLOAD_FORMAT {
+        return Err(DecodeError::UnsupportedFormat(bytes[0]));
+    }
+
+    let hundredths = i32::from(bytes[2] & 0x7F) * 100 + i32::from(bytes[3]);
+    let temperature = f64::from(hundredths) / 100.0;
+    let pressure = i32::from(u16::from_be_bytes([bytes[4], bytes[5]])) + 50000;
+
+    Ok(DataFormatLegacy {
+        format: if identifier.is_some() { 4 } else { 2 },
+        temperature: if bytes[2] & 0x80 == 0 {
+            temperature
+        } else {
+            -temperature
+        },
+        humidity: f64::from(bytes[1]) * 0.5,
+        pressure: f64::from(pressure) / 100.0,
+        identifier,
+    })
+}
+
+/// Decode a format 2 or 4 Ruuvi URL into [`RuuviData`]
+///
+/// Takes the part after the last `#`, or the whole input when there is none.
+/// [`RuuviData`] has no legacy variant, so the readings come back as a
+/// [`RuuviData::V5`] holding only temperature, humidity and pressure, with an
+/// [`UNKNOWN_MAC_ADDRESS`]. Use [`decode`] to keep the format and identifier.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{legacy, RuuviData};
+///
+/// let RuuviData::V5(data) = legacy::decode_url("https://ruu.vi/#AjwYAMFc").unwrap() else {
+///     unreachable!();
+/// };
+/// assert_eq!(data.temperature, Some(24.0));
+/// assert_eq!(data.humidity, Some(30.0));
+/// assert_eq!(data.pressure, Some(995.0));
+/// ```
+///
+/// # Errors
+///
+/// Same as [`decode`]
+pub fn decode_url(url: &str) -> Result<RuuviData> {
+    let fragment = url.rsplit_once('#').map_or(url, |(_, fragment)| fragment);
+    decode(fragment).map(|legacy| RuuviData::V5(legacy.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::format_2("AjwYAMFc", 2, 24.0, 30.0, 995.0, None)]
+    #[case::format_2_warm("AjgbAMFc", 2, 27.0, 28.0, 995.0, None)]
+    #[case::format_4("AjUX1MAw0", 4, 25.12, 26.5, 992.0, Some('0'))]
+    #[case::negative("AjyFMsFc", 2, -5.5, 30.0, 995.0, None)]
+    fn test_decode(
+        #[case] fragment: &str,
+        #[case] format: u8,
+        #[case] temperature: f64,
+        #[case] humidity: f64,
+        #[case] pressure: f64,
+        #[case] identifier: Option<char>,
+    ) {
+        assert_eq!(
+            decode(fragment).unwrap(),
+            DataFormatLegacy {
+                format,
+                temperature,
+                humidity,
+                pressure,
+                identifier,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("https://ruu.vi/#AjUX1MAw0")]
+    #[case("ruu.vi/#AjUX1MAw0")]
+    #[case("AjUX1MAw0")]
+    fn test_decode_url(#[case] url: &str) {
+        let RuuviData::V5(data) = decode_url(url).unwrap() else {
+            panic!("expected V5");
+        };
+        assert_eq!(data.temperature, Some(25.12));
+        assert_eq!(data.humidity, Some(26.5));
+        assert_eq!(data.pressure, Some(992.0));
+        assert_eq!(data.battery_voltage, None);
+        assert_eq!(data.mac_address, UNKNOWN_MAC_ADDRESS);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode(""), Err(DecodeError::NoData));
+        assert!(matches!(
+            decode("AjwYAM"),
+            Err(DecodeError::InvalidLength(_))
+        ));
+        assert!(matches!(
+            decode("AjwYAMFcAA"),
+            Err(DecodeError::InvalidLength(_))
+        ));
+        assert!(matches!(
+            decode("Ajw+AMFc"),
+            Err(DecodeError::InvalidBase64(_))
+        ));
+        assert_eq!(decode("BTwYAMFc"), Err(DecodeError::UnsupportedFormat(5)));
+        assert_eq!(decode_url("https://ruu.vi/#"), Err(DecodeError::NoData));
+    }
+}
@@ -0,0 +1,251 @@
+//! Decoding of damaged or oddly formatted captures
+//!
+//! Hex copied from sniffers and logs often carries separators, trailing
+//! bytes or a clipped MAC address. [`LenientDecoder`] cleans the input like
+//! [`crate::decode`] and then tries a configurable set of recoveries,
+//! reporting which one produced the reading.
+
+use crate::error::Result;
+use crate::ruuvi_data::{DataFormat, RuuviData};
+
+/// How [`LenientDecoder`] got a reading out of its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecoveryStrategy {
+    /// The payload decoded as is, after hex cleanup
+    Exact,
+    /// Bytes after the format's full payload length were dropped
+    Truncated,
+    /// A clipped MAC address was completed with zero bytes
+    ZeroPadded,
+    /// A valid payload was found at a later offset of the input
+    Embedded,
+}
+
+/// Decoder trying recovery strategies when strict decoding fails
+///
+/// Hex cleanup (`0x` prefix, whitespace, `:` and `-` separators) always
+/// applies, and the Ruuvi manufacturer data is located like [`crate::decode`]
+/// does, falling back to the whole input as a bare payload. Every recovery is
+/// off by default.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::lenient::{LenientDecoder, RecoveryStrategy};
+///
+/// let decoder = LenientDecoder::new().truncate(true);
+/// // Payload followed by an RSSI byte
+/// let (_data, strategy) = decoder
+///     .decode("05:12:FC:53:94:C3:7C:00:04:FF:FC:04:0C:AC:36:42:00:CD:CB:B8:33:4C:88:4F:C4")
+///     .unwrap();
+/// assert_eq!(strategy, RecoveryStrategy::Truncated);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LenientDecoder {
+    truncate: bool,
+    zero_pad: bool,
+    scan_embedded: bool,
+}
+
+impl LenientDecoder {
+    /// Create a decoder that only cleans up the hex
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop bytes beyond the full payload length of the detected format
+    #[must_use]
+    pub fn truncate(mut self, enabled: bool) -> Self {
+        self.truncate = enabled;
+        self
+    }
+
+    /// Complete a payload whose MAC address is cut short with zero bytes
+    ///
+    /// Only the MAC address is padded; a payload missing sensor bytes is
+    /// still rejected rather than decoded from made up values.
+    #[must_use]
+    pub fn zero_pad(mut self, enabled: bool) -> Self {
+        self.zero_pad = enabled;
+        self
+    }
+
+    /// Search every offset of the input for a decodable payload
+    ///
+    /// Linear in the input length, and any byte that happens to equal a
+    /// format byte followed by enough data decodes, so this can produce a
+    /// reading from data that is not Ruuvi at all. Tried last.
+    #[must_use]
+    pub fn scan_embedded(mut self, enabled: bool) -> Self {
+        self.scan_embedded = enabled;
+        self
+    }
+
+    /// Decode a hex string, trying the enabled recoveries in turn
+    ///
+    /// # Returns
+    ///
+    /// The reading and the [`RecoveryStrategy`] that produced it
+    ///
+    /// # Errors
+    ///
+    /// * `DecodeError::NoData` - Empty input
+    /// * `DecodeError::InvalidHex` - Invalid hex string
+    /// * Otherwise the error of strict decoding, when no recovery succeeds
+    pub fn decode(&self, input: &str) -> Result<(RuuviData, RecoveryStrategy)> {
+        let bytes = crate::hex_to_bytes(&crate::clean_hex(input)?)?;
+        let payload = crate::ruuvi_payload(&bytes).unwrap_or(&bytes);
+
+        let err = match RuuviData::decode(payload) {
+            Ok(data) => return Ok((data, RecoveryStrategy::Exact)),
+            Err(err) => err,
+        };
+
+        if let Some(format) = DataFormat::detect(payload) {
+            let full_length = format.payload_with_mac_length();
+            if self.truncate
+                && payload.len() > full_length
+                && let Ok(data) = RuuviData::decode(&payload[..full_length])
+            {
+                return Ok((data, RecoveryStrategy::Truncated));
+            }
+            if self.zero_pad && (format.payload_length()..full_length).contains(&payload.len()) {
+                let mut padded = payload.to_vec();
+                padded.resize(full_length, 0);
+                if let Ok(data) = RuuviData::decode(&padded) {
+                    return Ok((data, RecoveryStrategy::ZeroPadded));
+                }
+            }
+        }
+
+        if self.scan_embedded
+            && let Some(data) = scan(&bytes)
+        {
+            return Ok((data, RecoveryStrategy::Embedded));
+        }
+
+        Err(err)
+    }
+
+    /// Lazily decode every input, e.g. the lines of a capture file
+    ///
+    /// Blank inputs are skipped; the rest yield what [`Self::decode`] returns.
+    pub fn decode_iter<'a, I>(
+        &'a self,
+        inputs: I,
+    ) -> impl Iterator<Item = Result<(RuuviData, RecoveryStrategy)>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        inputs
+            .into_iter()
+            .filter(|input| !input.trim().is_empty())
+            .map(|input| self.decode(input))
+    }
+}
+
+/// First full-length payload that decodes, starting at any offset
+fn scan(bytes: &[u8]) -> Option<RuuviData> {
+    (1..bytes.len()).find_map(|start| {
+        let format = DataFormat::detect(&bytes[start..])?;
+        let payload = bytes.get(start..start + format.payload_with_mac_length())?;
+        RuuviData::decode(payload).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodeError;
+    use rstest::rstest;
+
+    const V5: &str = "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+
+    fn v5() -> RuuviData {
+        crate::decode_bytes(&hex::decode(V5).unwrap()).unwrap()
+    }
+
+    fn all() -> LenientDecoder {
+        LenientDecoder::new()
+            .truncate(true)
+            .zero_pad(true)
+            .scan_embedded(true)
+    }
+
+    #[rstest]
+    #[case::bare(V5)]
+    #[case::ble("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::separators("0x05-12-FC-53-94-C3-7C-00-04-FF-FC-04-0C-AC-36-42-00-CD CB:B8:33:4C:88:4F")]
+    fn test_exact(#[case] input: &str) {
+        assert_eq!(
+            LenientDecoder::new().decode(input),
+            Ok((v5(), RecoveryStrategy::Exact))
+        );
+    }
+
+    #[test]
+    fn test_truncated() {
+        let input = format!("{V5}C4C4");
+        assert!(matches!(
+            LenientDecoder::new().decode(&input),
+            Err(DecodeError::InvalidLength(_))
+        ));
+        assert_eq!(
+            all().decode(&input),
+            Ok((v5(), RecoveryStrategy::Truncated))
+        );
+    }
+
+    #[test]
+    fn test_zero_padded() {
+        let (data, strategy) = all().decode(&V5[..V5.len() - 4]).unwrap();
+        assert_eq!(strategy, RecoveryStrategy::ZeroPadded);
+        let RuuviData::V5(data) = data else {
+            panic!("expected V5");
+        };
+        assert_eq!(data.mac_address, "cbb8334c0000");
+
+        // Missing sensor bytes are not made up
+        assert!(all().decode(&V5[..30]).is_err());
+    }
+
+    #[test]
+    fn test_embedded_requires_flag() {
+        let input = format!("AABBCC{V5}");
+        let strict = LenientDecoder::new().truncate(true).zero_pad(true);
+        assert_eq!(
+            strict.decode(&input),
+            Err(DecodeError::UnsupportedFormat(0xAA))
+        );
+        assert_eq!(all().decode(&input), Ok((v5(), RecoveryStrategy::Embedded)));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(all().decode("  "), Err(DecodeError::NoData));
+        assert!(matches!(
+            all().decode("zz"),
+            Err(DecodeError::InvalidHex(_))
+        ));
+        assert_eq!(
+            all().decode("AABBCC"),
+            Err(DecodeError::UnsupportedFormat(0xAA))
+        );
+    }
+
+    #[test]
+    fn test_decode_iter() {
+        let input = format!("{V5}C4");
+        let decoder = LenientDecoder::new().truncate(true);
+        let results: Vec<_> = decoder
+            .decode_iter([V5, "", input.as_str(), "zz"])
+            .collect();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok((v5(), RecoveryStrategy::Exact)));
+        assert_eq!(results[1], Ok((v5(), RecoveryStrategy::Truncated)));
+        assert!(results[2].is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! This crate provides decoders for Ruuvi sensor BLE advertisements supporting:
 //! - Data Format 5 (`RAWv2`)
 //! - Data Format 6 (`RAWv3`)
-//! - Data Format E1 (Encrypted)
+//! - Data Format E1 (Extended v1)
 //!
 //! # Example
 //!
@@ -22,20 +22,58 @@
 //! }
 //! ```
 
+pub mod aggregate;
 pub mod air_quality;
+pub mod batch;
+pub mod ble;
+pub mod cache;
+pub mod decoder;
+pub mod diagnostics;
 pub mod e1;
+pub mod encode;
 pub mod error;
+#[cfg(feature = "cffi")]
+pub mod ffi;
+pub mod flags;
+pub mod history;
+#[cfg(feature = "base64")]
+pub mod legacy;
+pub mod lenient;
+pub mod mac;
+pub mod metadata;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod normalized;
+mod prometheus;
+pub mod readings;
 pub mod ruuvi_data;
+pub mod sequence;
+pub mod stats;
+pub mod units;
 pub mod v5;
 pub mod v6;
+pub mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+use std::io::{self, Read};
+
+pub use batch::{BatchResult, decode_batch};
+pub use diagnostics::FieldStatus;
 pub use error::{DecodeError, Result};
-pub use ruuvi_data::{DataFormat, RuuviData};
+pub use mac::MacAddress;
+pub use normalized::NormalizedReading;
+pub use readings::ReadingValue;
+pub use ruuvi_data::{DataFormat, RuuviData, SensorModel};
 
 pub const MANUFACTURER_ID_LENGTH: usize = 2;
 
 /// Main entry point for decoding Ruuvi BLE advertisement data
 ///
+/// An optional `0x` prefix, whitespace and `:`/`-` byte separators are ignored.
+/// The Ruuvi manufacturer data is located by walking the AD structures, so it
+/// may follow Flags or any other AD structure in the advertisement.
+///
 /// # Arguments
 ///
 /// * `hex_data` - Hex string of the Ruuvi payload (without the 9904 manufacturer prefix)
@@ -57,63 +95,687 @@ pub const MANUFACTURER_ID_LENGTH: usize = 2;
 /// # Errors
 ///
 /// * `DecodeError::InvalidHex` - Invalid hex string
+/// * `DecodeError::MissingManufacturerId` - No Ruuvi manufacturer data found
+/// * `DecodeError::NoData` - Manufacturer ID without a payload
 /// * `DecodeError::InvalidLength` - Invalid length of hex string
 /// * `DecodeError::UnsupportedFormat` - Unsupported data format
 pub fn decode(ble_data: &str) -> Result<RuuviData> {
-    let clean_data = ble_data
-        .trim()
-        .trim_start_matches("0x")
-        .replace(' ', "")
-        .to_uppercase();
+    let bytes = hex_to_bytes(&clean_hex(ble_data)?)?;
+    RuuviData::decode(ruuvi_payload(&bytes)?)
+}
 
-    if clean_data.is_empty() {
+/// Decode like [`decode`], also reporting why fields are missing
+///
+/// A `None` field alone does not say whether the sensor sent the "not
+/// available" sentinel, e.g. because it is not fitted, or a value beyond the
+/// format range, e.g. after a transient error. The returned [`FieldStatus`]
+/// marks each sensor field as present, invalid or out of range.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::decode_with_diagnostics;
+/// use ruuvi_decoders::diagnostics::FieldState;
+///
+/// let (_data, status) =
+///     decode_with_diagnostics("0201061BFF99040512FCFFFFC37C0004FFFC040CAC364200CDCBB8334C884F")
+///         .unwrap();
+/// assert_eq!(status.get("humidity"), Some(FieldState::Invalid));
+/// assert_eq!(status.get("temperature"), Some(FieldState::Present));
+/// ```
+///
+/// # Errors
+///
+/// Same as [`decode`]
+pub fn decode_with_diagnostics(ble_data: &str) -> Result<(RuuviData, FieldStatus)> {
+    let bytes = hex_to_bytes(&clean_hex(ble_data)?)?;
+    let payload = ruuvi_payload(&bytes)?;
+    let data = RuuviData::decode(payload)?;
+    let status = FieldStatus::new(payload, &data);
+    Ok((data, status))
+}
+
+/// Decode like [`decode`], snapping float fields to the sensor resolution
+///
+/// The scale factors of the formats leave float noise in the decoded values,
+/// e.g. a temperature of `24.300000000000004`. Rounding to the resolution of
+/// each field (0.005°C, 0.0025% RH, 1 Pa, ...) gives `24.3` instead. See
+/// [`RuuviData::rounded`].
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_rounded, RuuviData};
+///
+/// let data =
+///     decode_rounded("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// if let RuuviData::V5(v5) = data {
+///     assert_eq!(v5.temperature, Some(24.3));
+///     assert_eq!(v5.humidity, Some(53.49));
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Same as [`decode`]
+pub fn decode_rounded(ble_data: &str) -> Result<RuuviData> {
+    decode(ble_data).map(|data| data.rounded())
+}
+
+/// Decode like [`decode`], also returning the detected data format
+///
+/// Handy for routing readings by format without matching on [`RuuviData`].
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_tagged, DataFormat};
+///
+/// let (format, _data) =
+///     decode_tagged("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert_eq!(format, DataFormat::V5);
+/// ```
+///
+/// # Errors
+///
+/// Same as [`decode`]
+pub fn decode_tagged(ble_data: &str) -> Result<(DataFormat, RuuviData)> {
+    let data = decode(ble_data)?;
+    Ok((data.format(), data))
+}
+
+/// Decode a full BLE advertisement or a bare Ruuvi payload, whichever `input` is
+///
+/// Tries the Ruuvi manufacturer data first, like [`decode`], and falls back to
+/// decoding `input` directly as a payload starting with the format byte, like
+/// [`decode_bytes`]. Useful when the caller cannot tell which of the two it has.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::decode_auto;
+///
+/// let from_ble = decode_auto("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// let from_payload = decode_auto("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert_eq!(from_ble, from_payload);
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidHex` - Invalid hex string
+/// * `DecodeError::NoData` - Empty input
+/// * Otherwise the error of the manufacturer data, if any was found, or else
+///   the error of decoding `input` directly
+pub fn decode_auto(input: &str) -> Result<RuuviData> {
+    let bytes = hex_to_bytes(&clean_hex(input)?)?;
+    if bytes.is_empty() {
         return Err(DecodeError::NoData);
     }
 
-    if !clean_data.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(DecodeError::InvalidData(format!(
-            "Contains non hex characters: {clean_data}"
-        )));
+    match find_manufacturer_payload(&bytes, ble::RUUVI_COMPANY_ID) {
+        Some(payload) if !payload.is_empty() => {
+            RuuviData::decode(payload).or_else(|err| RuuviData::decode(&bytes).map_err(|_| err))
+        }
+        _ => RuuviData::decode(&bytes),
     }
+}
 
-    let payload_start = validate_ruuvi_manufacturer_id(ble_data)?;
-    let payload_start = payload_start / 2; // to byte index
-    // Convert hex to bytes
-    let bytes = hex_to_bytes(&clean_data)?;
+/// Decode a raw Ruuvi payload without any hex parsing
+///
+/// This is the fast path for scanners that already hold the manufacturer data
+/// as bytes: no intermediate buffers are allocated, the only allocation is the
+/// MAC address string of the decoded struct.
+///
+/// # Arguments
+///
+/// * `bytes` - Ruuvi payload starting with the format byte (without the 9904 manufacturer ID)
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_bytes, RuuviData};
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert!(matches!(decode_bytes(&bytes), Ok(RuuviData::V5(_))));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - Invalid payload length for the format
+/// * `DecodeError::UnsupportedFormat` - Unsupported data format
+pub fn decode_bytes(bytes: &[u8]) -> Result<RuuviData> {
+    RuuviData::decode(bytes)
+}
 
-    if bytes.is_empty() {
-        return Err(DecodeError::InvalidData(format!(
-            "No bytes provided in hex_string: {clean_data}"
-        )));
+/// Decode a raw Ruuvi payload that may carry one extra trailing byte
+///
+/// Some sniffers append an RSSI or CRC byte to the manufacturer data. This
+/// accepts the exact payload length of the format, or one byte more, in
+/// which case the last byte is ignored. [`decode_bytes`] stays strict.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_bytes, decode_lenient_length};
+///
+/// // Data Format 5 payload followed by an RSSI byte
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884FC4").unwrap();
+/// assert!(decode_bytes(&bytes).is_err());
+/// assert_eq!(decode_lenient_length(&bytes), decode_bytes(&bytes[..24]));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - Length is neither the exact nor the exact + 1 length
+/// * `DecodeError::UnsupportedFormat` - Unsupported data format
+pub fn decode_lenient_length(bytes: &[u8]) -> Result<RuuviData> {
+    if let Some(format) = DataFormat::detect(bytes)
+        && format.validate_length(bytes).is_err()
+        && format.validate_length(&bytes[..bytes.len() - 1]).is_ok()
+    {
+        return RuuviData::decode(&bytes[..bytes.len() - 1]);
     }
 
-    // Do not calculate length from bytes, use payload_start instead for now
-    // let Some(length) = bytes.get(0) else {
-    //     return Err(DecodeError::InvalidLength(
-    //         "Got bytes, but length byte is missing".into(),
-    //     ));
-    // };
+    RuuviData::decode(bytes)
+}
+
+/// Decode a raw Ruuvi payload as the given format, ignoring its format byte
+///
+/// For gateways that strip or rewrite the format byte, or when the format is
+/// known out of band. `bytes` may either be the full payload, whose first byte
+/// is then ignored, or the payload with the format byte stripped.
+///
+/// # Arguments
+///
+/// * `format` - Data format to decode the payload as
+/// * `bytes` - Ruuvi payload, with or without its format byte
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_with_format, DataFormat, RuuviData};
+///
+/// // Format byte corrupted to 0x00
+/// let bytes = hex::decode("0012FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// let data = decode_with_format(DataFormat::V5, &bytes).unwrap();
+/// assert!(matches!(data, RuuviData::V5(_)));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - Length does not match the format, with or without the format byte
+pub fn decode_with_format(format: DataFormat, bytes: &[u8]) -> Result<RuuviData> {
+    let fields = if format.validate_length(bytes).is_ok() {
+        &bytes[1..]
+    } else {
+        bytes
+    };
+
+    let mut payload = Vec::with_capacity(fields.len() + 1);
+    payload.push(format as u8);
+    payload.extend_from_slice(fields);
+    format
+        .validate_length(&payload)
+        .map_err(|_| DecodeError::invalid_length(format.payload_with_mac_length(), bytes.len()))?;
 
-    // bytes[1..3] = Manufacturer Specified Data
-    // bytes[3..5] = Ruuvi Manufacturer ID
-    let bytes = &bytes[payload_start..];
+    RuuviData::decode(&payload)
+}
 
-    // Determine data format from first byte
-    match bytes[0] {
-        5 => {
-            let data = v5::decode(bytes)?;
-            Ok(RuuviData::V5(data))
-        }
-        6 => {
-            let data = v6::decode(bytes)?;
-            Ok(RuuviData::V6(data))
+/// Read one binary payload of `format` from `reader` and decode it
+///
+/// Reads exactly [`DataFormat::payload_with_mac_length`] bytes, format byte
+/// included, so consecutive calls walk through a capture of back-to-back
+/// payloads. The reader is left positioned after the payload.
+///
+/// # Arguments
+///
+/// * `reader` - Source of raw payload bytes, e.g. a capture file
+/// * `format` - Data format of the payload
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use ruuvi_decoders::{decode_from_reader, DataFormat, RuuviData};
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// let data = decode_from_reader(&mut Cursor::new(bytes), DataFormat::V5).unwrap();
+/// assert!(matches!(data, RuuviData::V5(_)));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidLength` - The reader ended before a full payload
+/// * `DecodeError::Io` - Reading failed for another reason
+/// * `DecodeError::UnsupportedFormat` - The payload's format byte is not `format`
+/// * Any error returned by the format specific decoder
+pub fn decode_from_reader<R: Read>(reader: &mut R, format: DataFormat) -> Result<RuuviData> {
+    let mut payload = vec![0; format.payload_with_mac_length()];
+    reader.read_exact(&mut payload).map_err(|err| {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            DecodeError::InvalidLength(format!(
+                "Stream ended before a {} byte {format} payload",
+                payload.len()
+            ))
+        } else {
+            DecodeError::Io(err.to_string())
         }
-        0xE1 => {
-            let data = e1::decode(bytes)?;
-            Ok(RuuviData::E1(data))
+    })?;
+
+    if payload[0] != format as u8 {
+        return Err(DecodeError::UnsupportedFormat(payload[0]));
+    }
+    RuuviData::decode(&payload)
+}
+
+/// Decode base64 encoded Ruuvi manufacturer data, as delivered by some MQTT bridges
+///
+/// The decoded bytes may start with the Ruuvi company ID (`99 04`), which is
+/// skipped, or directly with the format byte.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_base64, RuuviData};
+///
+/// let data = decode_base64("BRL8U5TDfAAE//wEDKw2QgDNy7gzTIhP").unwrap();
+/// assert!(matches!(data, RuuviData::V5(_)));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::NoData` - No payload bytes
+/// * `DecodeError::InvalidBase64` - Invalid base64 string
+/// * `DecodeError::InvalidLength` - Invalid payload length for the format
+/// * `DecodeError::UnsupportedFormat` - Unsupported data format
+#[cfg(feature = "base64")]
+pub fn decode_base64(s: &str) -> Result<RuuviData> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(s.trim())
+        .map_err(|err| DecodeError::InvalidBase64(err.to_string()))?;
+    let payload = bytes
+        .strip_prefix(&ble::RUUVI_COMPANY_ID.to_le_bytes())
+        .unwrap_or(&bytes);
+
+    if payload.is_empty() {
+        return Err(DecodeError::NoData);
+    }
+
+    RuuviData::decode(payload)
+}
+
+/// Decode a Ruuvi payload hex string using a caller provided buffer
+///
+/// The hex is decoded into `buf` instead of a freshly allocated `Vec`, so a
+/// scanner can reuse one buffer for every packet. Unlike [`decode`], separators
+/// are not stripped; only surrounding whitespace is ignored.
+///
+/// # Arguments
+///
+/// * `hex` - Hex string of the Ruuvi payload starting with the format byte
+/// * `buf` - Scratch buffer, at least half as long as the hex string
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_hex_into, RuuviData};
+///
+/// let mut buf = [0u8; 64];
+/// let data = decode_hex_into("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F", &mut buf);
+/// assert!(matches!(data, Ok(RuuviData::V5(_))));
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::NoData` - Empty hex string
+/// * `DecodeError::InvalidHex` - Invalid hex string
+/// * `DecodeError::InvalidLength` - `buf` is too small, or invalid payload length
+/// * `DecodeError::UnsupportedFormat` - Unsupported data format
+pub fn decode_hex_into(hex: &str, buf: &mut [u8]) -> Result<RuuviData> {
+    let hex = hex.trim();
+
+    if hex.is_empty() {
+        return Err(DecodeError::NoData);
+    }
+
+    if !hex.len().is_multiple_of(2) {
+        return Err(hex::FromHexError::OddLength.into());
+    }
+
+    let len = hex.len() / 2;
+    let Some(buf) = buf.get_mut(..len) else {
+        return Err(DecodeError::InvalidLength(format!(
+            "Buffer of {} bytes cannot hold {len} bytes",
+            buf.len()
+        )));
+    };
+
+    hex::decode_to_slice(hex, buf)?;
+
+    RuuviData::decode(buf)
+}
+
+/// Decode and reject physically implausible readings
+///
+/// Works like [`decode`], then runs the range checks from [`validation`], e.g.
+/// humidity above 100.1% or pressure outside 300..1100 hPa.
+///
+/// # Errors
+///
+/// * Any error returned by [`decode`]
+/// * `DecodeError::ValidationFailed` - A decoded reading is not plausible
+pub fn decode_validated(ble_data: &str) -> Result<RuuviData> {
+    let data = decode(ble_data)?;
+    validation::validate(&data)?;
+    Ok(data)
+}
+
+/// Decode every Ruuvi manufacturer segment found in a BLE capture
+///
+/// Scans the whole input for the Ruuvi manufacturer ID (`9904` or `0499`) and
+/// decodes the payload following each occurrence. When the ID is preceded by a
+/// well-formed AD structure header (`<length> FF`) that fits in the input, the
+/// AD length bounds the payload; otherwise the expected length of the detected
+/// data format is used. Scanning resumes after each segment, so ID-like bytes
+/// inside a payload (e.g. in a MAC address) are not mistaken for a new segment.
+///
+/// # Arguments
+///
+/// * `ble_data` - Hex string of one or more concatenated BLE advertisements
+///
+/// # Returns
+///
+/// One decode result per manufacturer segment, in input order. Input that is
+/// not valid hex yields a single error; input without any Ruuvi segment yields
+/// an empty vector.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_all, RuuviData};
+///
+/// let capture = concat!(
+///     "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+///     "020106",
+///     "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+/// );
+/// let results = decode_all(capture);
+///
+/// assert_eq!(results.len(), 2);
+/// assert!(matches!(results[0], Ok(RuuviData::V5(_))));
+/// assert!(matches!(results[1], Ok(RuuviData::V6(_))));
+/// ```
+#[must_use]
+pub fn decode_all(ble_data: &str) -> Vec<Result<RuuviData>> {
+    let bytes = match clean_hex(ble_data).and_then(|clean_data| hex_to_bytes(&clean_data)) {
+        Ok(bytes) => bytes,
+        Err(err) => return vec![Err(err)],
+    };
+
+    decode_scan_buffer(&bytes)
+}
+
+/// Decode every Ruuvi manufacturer segment found in raw advertisement bytes
+///
+/// The binary counterpart of [`decode_all`], for scanners that collect raw
+/// advertisements back to back, e.g. in a ring buffer. Segments are located
+/// the same way. A frame cut off at the end of `bytes` yields an error for
+/// its segment, `DecodeError::InvalidLength` or `DecodeError::NoData`, so the
+/// caller can keep its bytes for the next read.
+///
+/// # Arguments
+///
+/// * `bytes` - One or more concatenated BLE advertisements
+///
+/// # Returns
+///
+/// One decode result per manufacturer segment, in buffer order
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_scan_buffer, DecodeError, RuuviData};
+///
+/// let mut buffer = hex::decode(concat!(
+///     "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+///     "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+/// ))
+/// .unwrap();
+/// // Start of the next frame
+/// buffer.extend([0x1B, 0xFF, 0x99, 0x04, 0x05, 0x12]);
+///
+/// let results = decode_scan_buffer(&buffer);
+/// assert!(matches!(results[0], Ok(RuuviData::V5(_))));
+/// assert!(matches!(results[1], Ok(RuuviData::V6(_))));
+/// assert!(matches!(results[2], Err(DecodeError::InvalidLength(_))));
+/// ```
+#[must_use]
+pub fn decode_scan_buffer(bytes: &[u8]) -> Vec<Result<RuuviData>> {
+    ruuvi_segments(bytes)
+        .into_iter()
+        .map(|segment| {
+            if segment.is_empty() {
+                Err(DecodeError::NoData)
+            } else {
+                RuuviData::decode(segment)
+            }
+        })
+        .collect()
+}
+
+/// Extract the first Ruuvi payload from a full BLE advertisement
+///
+/// Walks the AD structures of the advertisement (see [`ble::AdStructures`]) and
+/// returns the payload of the first Manufacturer Specific Data structure with
+/// the Ruuvi company ID, as an uppercase hex string starting with the format
+/// byte. Input that is not well-formed AD data, such as some gateway dumps, is
+/// scanned for the manufacturer ID the same way [`decode_all`] does.
+///
+/// # Arguments
+///
+/// * `ble_data` - Full BLE advertisement hex string
+///
+/// # Returns
+///
+/// * `Some(String)` - Extracted Ruuvi payload hex
+/// * `None` - No Ruuvi data found
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::extract_ruuvi_from_ble;
+///
+/// let ble_data = "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+/// let payload = extract_ruuvi_from_ble(ble_data).unwrap();
+/// assert_eq!(payload, "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F");
+/// ```
+#[must_use]
+pub fn extract_ruuvi_from_ble(ble_data: &str) -> Option<String> {
+    extract_manufacturer_payload(ble_data, ble::RUUVI_COMPANY_ID)
+}
+
+/// Reassemble Ruuvi manufacturer data split across BLE 5 extended advertising fragments
+///
+/// Scanners may surface the `AUX_ADV_IND` / `AUX_CHAIN_IND` chain of an
+/// extended advertisement as separate fragments. Each fragment must carry a
+/// piece of the Ruuvi manufacturer data; the pieces are concatenated in the
+/// given order.
+///
+/// # Arguments
+///
+/// * `fragments` - BLE advertisement hex strings, in chain order
+///
+/// # Returns
+///
+/// * `Some(String)` - Reassembled payload hex, like [`extract_ruuvi_from_ble`]
+/// * `None` - No fragments, or a fragment without Ruuvi manufacturer data
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode_auto, reassemble_extended};
+///
+/// let payload = reassemble_extended(&[
+///     "02010610FF99040512FC5394C37C0004FFFC040C",
+///     "0EFF9904AC364200CDCBB8334C884F",
+/// ])
+/// .unwrap();
+/// assert!(decode_auto(&payload).is_ok());
+/// ```
+#[must_use]
+pub fn reassemble_extended(fragments: &[&str]) -> Option<String> {
+    if fragments.is_empty() {
+        return None;
+    }
+
+    fragments
+        .iter()
+        .map(|fragment| extract_ruuvi_from_ble(fragment))
+        .collect()
+}
+
+/// Extract the first Manufacturer Specific Data payload for `company_id`
+///
+/// For bridges and white-labeled sensors that use a company ID other than
+/// Ruuvi's. `company_id` is the 16-bit Bluetooth SIG identifier, e.g. `0x0499`;
+/// it is matched in the little-endian byte order used on air. Only Ruuvi's
+/// company ID also gets the manufacturer ID scan fallback of
+/// [`extract_ruuvi_from_ble`] for input that is not well-formed AD data.
+///
+/// # Arguments
+///
+/// * `ble_data` - Full BLE advertisement hex string
+/// * `company_id` - Company identifier to look for
+///
+/// # Returns
+///
+/// * `Some(String)` - Extracted payload hex, without the company ID
+/// * `None` - No manufacturer data with this company ID found
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::extract_manufacturer_payload;
+///
+/// let ble_data = "02010607FF3412DEADBEEF";
+/// let payload = extract_manufacturer_payload(ble_data, 0x1234).unwrap();
+/// assert_eq!(payload, "DEADBEEF");
+/// ```
+#[must_use]
+pub fn extract_manufacturer_payload(ble_data: &str, company_id: u16) -> Option<String> {
+    let bytes = clean_hex(ble_data)
+        .and_then(|clean_data| hex_to_bytes(&clean_data))
+        .ok()?;
+
+    find_manufacturer_payload(&bytes, company_id)
+        .filter(|payload| !payload.is_empty())
+        .map(hex::encode_upper)
+}
+
+/// The Ruuvi manufacturer payload of a BLE capture, as [`decode`] requires it
+fn ruuvi_payload(bytes: &[u8]) -> Result<&[u8]> {
+    let payload = find_manufacturer_payload(bytes, ble::RUUVI_COMPANY_ID)
+        .ok_or(DecodeError::MissingManufacturerId)?;
+    if payload.is_empty() {
+        return Err(DecodeError::NoData);
+    }
+    Ok(payload)
+}
+
+/// Locate the first manufacturer payload for `company_id`, wherever it sits
+///
+/// Manufacturer Specific Data found by walking the AD structures wins, so the
+/// payload may follow Flags, a local name or any other AD structure. For the
+/// Ruuvi company ID, input that is not well-formed AD data falls back to
+/// scanning for the manufacturer ID with [`ruuvi_segments`].
+fn find_manufacturer_payload(bytes: &[u8], company_id: u16) -> Option<&[u8]> {
+    let from_ad = ble::AdStructures::parse(bytes)
+        .filter_map(|ad| ad.manufacturer_data())
+        .find(|(id, payload)| *id == company_id && !payload.is_empty())
+        .map(|(_, payload)| payload);
+
+    if from_ad.is_some() || company_id != ble::RUUVI_COMPANY_ID {
+        return from_ad;
+    }
+
+    let segments = ruuvi_segments(bytes);
+    segments
+        .iter()
+        .copied()
+        .find(|segment| !segment.is_empty())
+        .or_else(|| segments.first().copied())
+}
+
+/// Find the payload following every Ruuvi manufacturer ID in `bytes`
+///
+/// A manufacturer ID at the very end of the input yields an empty payload.
+fn ruuvi_segments(bytes: &[u8]) -> Vec<&[u8]> {
+    let mut segments = Vec::new();
+    let mut idx = 0;
+
+    while idx + MANUFACTURER_ID_LENGTH <= bytes.len() {
+        if !matches!(
+            bytes[idx..idx + MANUFACTURER_ID_LENGTH],
+            [0x99, 0x04] | [0x04, 0x99]
+        ) {
+            idx += 1;
+            continue;
         }
-        format => Err(DecodeError::UnsupportedFormat(format)),
+
+        let payload_start = idx + MANUFACTURER_ID_LENGTH;
+        let Some(&format) = bytes.get(payload_start) else {
+            segments.push(&bytes[payload_start..]);
+            break;
+        };
+
+        // Standard AD structure: <length> 0xFF <company id> <payload>, where
+        // length covers the type byte, the company ID and the payload. A length
+        // running past the end of the input is not trusted.
+        let ad_end =
+            (idx >= 2 && bytes[idx - 1] == 0xFF).then(|| idx - 1 + usize::from(bytes[idx - 2]));
+        let payload_end = ad_end
+            .filter(|&end| end > payload_start && end <= bytes.len())
+            .or_else(|| {
+                DataFormat::from_u8(format).map(|f| payload_start + f.payload_with_mac_length())
+            })
+            .map_or(payload_start + 1, |end| end.min(bytes.len()));
+
+        segments.push(&bytes[payload_start..payload_end]);
+        idx = payload_end;
     }
+
+    segments
+}
+
+/// Lazily decode a stream of hex lines, e.g. read from a log file
+///
+/// Blank lines are skipped. Every other line is decoded with [`decode`] and
+/// returned together with its input, so failures can be traced back to the
+/// offending line.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::decode_stream;
+///
+/// let log = "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F\n\nnot hex\n";
+/// let results: Vec<_> = decode_stream(log.lines().map(String::from)).collect();
+///
+/// assert_eq!(results.len(), 2);
+/// assert!(results[0].1.is_ok());
+/// assert_eq!(results[1].0, "not hex");
+/// assert!(results[1].1.is_err());
+/// ```
+pub fn decode_stream<I>(lines: I) -> impl Iterator<Item = (String, Result<RuuviData>)>
+where
+    I: Iterator<Item = String>,
+{
+    lines.filter(|line| !line.trim().is_empty()).map(|line| {
+        let result = decode(&line);
+        (line, result)
+    })
 }
 
 /// Extract Ruuvi data from a full BLE advertisement
@@ -151,21 +813,65 @@ pub fn validate_ruuvi_manufacturer_id(ble_data: &str) -> Result<usize> {
     Err(DecodeError::MissingManufacturerId)
 }
 
+/// Normalize a user supplied hex string
+///
+/// Strips an optional `0x` prefix, whitespace (including internal line breaks)
+/// and the `:`/`-` separators commonly used when copying bytes from tools.
+/// A character that is not a hex digit is reported as written and at its
+/// index in `ble_data`, not in the cleaned string.
+fn clean_hex(ble_data: &str) -> Result<String> {
+    let trimmed = ble_data.trim();
+    let without_prefix = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    let offset = ble_data[..ble_data.len() - ble_data.trim_start().len()]
+        .chars()
+        .count()
+        + trimmed.len()
+        - without_prefix.len();
+
+    let mut clean_data = String::with_capacity(without_prefix.len());
+    for (index, c) in without_prefix.chars().enumerate() {
+        if c.is_whitespace() || c == ':' || c == '-' {
+            continue;
+        }
+        if !c.is_ascii_hexdigit() {
+            return Err(hex::FromHexError::InvalidHexCharacter {
+                c,
+                index: offset + index,
+            }
+            .into());
+        }
+        clean_data.push(c.to_ascii_uppercase());
+    }
+
+    if clean_data.is_empty() {
+        return Err(DecodeError::NoData);
+    }
+
+    Ok(clean_data)
+}
+
 /// Convert hex string to bytes
 fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>> {
-    if !hex_str.len().is_multiple_of(2) {
-        return Err(DecodeError::InvalidHex(format!(
-            "Odd number of hex characters: {}",
-            hex_str.len()
-        )));
+    // Report a bad character before an odd length, whichever comes first
+    if let Some((index, c)) = hex_str
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_hexdigit())
+    {
+        return Err(hex::FromHexError::InvalidHexCharacter { c, index }.into());
     }
 
-    hex::decode(hex_str).map_err(|_| DecodeError::InvalidHex(hex_str.to_string()))
+    Ok(hex::decode(hex_str)?)
 }
 
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
+    use proptest::prelude::*;
+    use rstest::rstest;
 
     use super::*;
 
@@ -177,6 +883,140 @@ mod tests {
         assert_debug_snapshot!(data);
     }
 
+    #[rstest]
+    #[case("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case(concat!(
+        "020106",
+        "06095275757669",
+        "1BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F"
+    ))]
+    fn test_decode_after_other_ad_structures(#[case] ble_data: &str) {
+        let expected =
+            decode_bytes(&hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap());
+        assert_eq!(decode(ble_data), expected);
+    }
+
+    #[test]
+    fn test_decode_missing_manufacturer_id() {
+        assert_eq!(
+            decode("020106030316910255AA"),
+            Err(DecodeError::MissingManufacturerId)
+        );
+        assert_eq!(decode("0201069904"), Err(DecodeError::NoData));
+    }
+
+    #[rstest]
+    #[case(
+        "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        DataFormat::V5
+    )]
+    #[case("17FF990406170C5668C79E007000C90501D9FFCD004C884F", DataFormat::V6)]
+    #[case(
+        "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        DataFormat::E1
+    )]
+    fn test_decode_tagged(#[case] ble_data: &str, #[case] expected: DataFormat) {
+        let (format, data) = decode_tagged(ble_data).unwrap();
+        assert_eq!(format, expected);
+        assert_eq!(data, decode(ble_data).unwrap());
+    }
+
+    #[rstest]
+    #[case("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("17FF990406170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case(
+        "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+    )]
+    fn test_decode_rounded(#[case] ble_data: &str) {
+        let rounded = decode_rounded(ble_data).unwrap();
+        assert_eq!(rounded.rounded(), rounded);
+        assert_eq!(rounded.encode(), decode(ble_data).unwrap().encode());
+    }
+
+    #[test]
+    fn test_decode_from_reader() {
+        let v5 = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let max = hex::decode("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F").unwrap();
+        let mut reader =
+            std::io::Cursor::new([v5.clone(), max.clone(), v5[..10].to_vec()].concat());
+
+        assert_eq!(
+            decode_from_reader(&mut reader, DataFormat::V5),
+            RuuviData::decode(&v5)
+        );
+        assert_eq!(
+            decode_from_reader(&mut reader, DataFormat::V5),
+            RuuviData::decode(&max)
+        );
+        assert!(matches!(
+            decode_from_reader(&mut reader, DataFormat::V5),
+            Err(DecodeError::InvalidLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_from_reader_errors() {
+        let v6 = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        assert_eq!(
+            decode_from_reader(&mut v6.as_slice(), DataFormat::V6).map(|data| data.format()),
+            Ok(DataFormat::V6)
+        );
+
+        let v5 = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(
+            decode_from_reader(&mut v5.as_slice(), DataFormat::V6),
+            Err(DecodeError::UnsupportedFormat(5))
+        );
+
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("device unplugged"))
+            }
+        }
+        assert_eq!(
+            decode_from_reader(&mut Failing, DataFormat::V5),
+            Err(DecodeError::Io("device unplugged".into()))
+        );
+    }
+
+    #[test]
+    fn test_decode_rounded_values() {
+        let ble_data = "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F";
+        let RuuviData::E1(data) = decode_rounded(ble_data).unwrap() else {
+            panic!("expected E1");
+        };
+        assert_eq!(data.temperature, Some(29.5));
+        assert_eq!(data.pm1_0, Some(10.1));
+        assert_eq!(data.pm2_5, Some(11.2));
+        assert_eq!(data.luminosity, Some(13_027.0));
+    }
+
+    #[rstest]
+    #[case("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("05:12:FC:53:94:C3:7C:00:04:FF:FC:04:0C:AC:36:42:00:CD:CB:B8:33:4C:88:4F")]
+    fn test_decode_auto(#[case] input: &str) {
+        let expected = decode("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(decode_auto(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_decode_auto_errors() {
+        assert_eq!(decode_auto(""), Err(DecodeError::NoData));
+        assert!(matches!(decode_auto("XY"), Err(DecodeError::InvalidHex(_))));
+        assert_eq!(
+            decode_auto("63AABB"),
+            Err(DecodeError::UnsupportedFormat(0x63))
+        );
+        // Manufacturer data found but truncated: report that, not the fallback
+        assert!(matches!(
+            decode_auto("0201060DFF99040512FC5394C37C0004FF"),
+            Err(DecodeError::InvalidLength(_))
+        ));
+    }
+
     #[test]
     fn test_hex_to_bytes() {
         assert_eq!(hex_to_bytes("01FF").unwrap(), vec![0x01, 0xFF]);
@@ -185,6 +1025,70 @@ mod tests {
         assert!(hex_to_bytes("GG").is_err()); // Invalid hex
     }
 
+    #[test]
+    fn test_hex_to_bytes_reports_position() {
+        assert_eq!(
+            hex_to_bytes("01FG"),
+            Err(DecodeError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c: 'G', index: 3 }
+            ))
+        );
+        assert_eq!(
+            hex_to_bytes("01F"),
+            Err(DecodeError::InvalidHex(hex::FromHexError::OddLength))
+        );
+    }
+
+    #[rstest]
+    #[case("  0x18:FF:FF:99:04:g5", 'g', 19)]
+    #[case("18 ff-ff\n99 0x", 'x', 13)]
+    fn test_invalid_hex_reports_original_position(
+        #[case] ble_data: &str,
+        #[case] c: char,
+        #[case] index: usize,
+    ) {
+        assert_eq!(ble_data.chars().nth(index), Some(c));
+        assert_eq!(
+            decode(ble_data),
+            Err(DecodeError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c, index }
+            ))
+        );
+    }
+
+    #[rstest]
+    #[case("18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("0x18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case(
+        "  18 FF FF 99 04 05 12 FC 53 94 C3 7C 00 04 FF FC 04 0C AC 36 42 00 CD CB B8 33 4C 88 4F\n"
+    )]
+    #[case(
+        "18:FF:FF:99:04:05:12:FC:53:94:C3:7C:00:04:FF:FC:04:0C:AC:36:42:00:CD:CB:B8:33:4C:88:4F"
+    )]
+    #[case(
+        "18-ff-ff-99-04-05-12-fc-53-94-c3-7c-00-04-ff-fc-04-0c-ac-36-42-00-cd-cb-b8-33-4c-88-4f"
+    )]
+    #[case("18FFFF99040512FC5394C37C\n0004FFFC040CAC36\r\n4200CDCBB8334C884F")]
+    fn test_decode_separators(#[case] ble_data: &str) {
+        assert!(matches!(decode(ble_data), Ok(RuuviData::V5(_))));
+    }
+
+    #[test]
+    fn test_decode_invalid_hex() {
+        assert_eq!(
+            decode("invalid_hex"),
+            Err(DecodeError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c: 'i', index: 0 }
+            ))
+        );
+        assert_eq!(
+            decode("0x18FFFF9904051ZFC"),
+            Err(DecodeError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c: 'Z', index: 15 }
+            ))
+        );
+    }
+
     #[test]
     fn test_validate_ruuvi_ble_data() {
         let ble_data = "99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
@@ -212,6 +1116,338 @@ mod tests {
         assert_eq!(result, Err(DecodeError::UnsupportedFormat(99)));
     }
 
+    #[test]
+    fn test_decode_bytes() {
+        let bytes = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        assert!(matches!(decode_bytes(&bytes), Ok(RuuviData::V6(_))));
+        assert_eq!(
+            decode_bytes(&[0x63, 0x00]),
+            Err(DecodeError::UnsupportedFormat(0x63))
+        );
+    }
+
+    #[rstest]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    fn test_decode_lenient_length(#[case] hex_str: &str) {
+        let bytes = hex::decode(hex_str).unwrap();
+        let expected = decode_bytes(&bytes).unwrap();
+        assert_eq!(decode_lenient_length(&bytes), Ok(expected.clone()));
+
+        let mut with_trailer = bytes.clone();
+        with_trailer.push(0xC4);
+        assert!(decode_bytes(&with_trailer).is_err());
+        assert_eq!(decode_lenient_length(&with_trailer), Ok(expected));
+
+        with_trailer.push(0xC4);
+        assert!(matches!(
+            decode_lenient_length(&with_trailer),
+            Err(DecodeError::InvalidLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_hex_into() {
+        let hex_str = "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+        let mut buf = [0u8; 64];
+
+        let from_buf = decode_hex_into(hex_str, &mut buf).unwrap();
+        assert_eq!(
+            from_buf,
+            decode_bytes(&hex::decode(hex_str).unwrap()).unwrap()
+        );
+        assert_eq!(buf[..2], [0x05, 0x12]);
+
+        // Buffer is reused for the next packet
+        let e1_hex =
+            "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F";
+        assert!(matches!(
+            decode_hex_into(e1_hex, &mut buf),
+            Ok(RuuviData::E1(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_hex_into_errors() {
+        let mut buf = [0u8; 8];
+        assert_eq!(decode_hex_into("  ", &mut buf), Err(DecodeError::NoData));
+        assert_eq!(
+            decode_hex_into("051", &mut buf),
+            Err(DecodeError::InvalidHex(hex::FromHexError::OddLength))
+        );
+        assert_eq!(
+            decode_hex_into("05XY", &mut buf),
+            Err(DecodeError::InvalidHex(
+                hex::FromHexError::InvalidHexCharacter { c: 'X', index: 2 }
+            ))
+        );
+        assert_eq!(
+            decode_hex_into("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F", &mut buf),
+            Err(DecodeError::InvalidLength(
+                "Buffer of 8 bytes cannot hold 24 bytes".into()
+            ))
+        );
+    }
+
+    #[rstest]
+    #[case(DataFormat::V5, "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case(DataFormat::V5, "FF12FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")] // corrupted
+    #[case(DataFormat::V5, "12FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")] // stripped
+    #[case(DataFormat::V6, "00170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case(DataFormat::V6, "170C5668C79E007000C90501D9FFCD004C884F")]
+    fn test_decode_with_format(#[case] format: DataFormat, #[case] hex_str: &str) {
+        let bytes = hex::decode(hex_str).unwrap();
+        let data = decode_with_format(format, &bytes).unwrap();
+
+        let mut canonical = bytes.clone();
+        if canonical.len() < format.payload_with_mac_length() {
+            canonical.insert(0, 0);
+        }
+        canonical[0] = format as u8;
+        assert_eq!(data, decode_bytes(&canonical).unwrap());
+    }
+
+    #[test]
+    fn test_decode_with_format_length() {
+        assert_eq!(
+            decode_with_format(DataFormat::V6, &[0x06; 24]),
+            Err(DecodeError::invalid_length(20, 24))
+        );
+        assert_eq!(
+            decode_with_format(DataFormat::V5, &[]),
+            Err(DecodeError::invalid_length(24, 0))
+        );
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_decode_base64() {
+        let expected = decode("18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F");
+
+        // Payload only, and payload with the company ID
+        assert_eq!(decode_base64("BRL8U5TDfAAE//wEDKw2QgDNy7gzTIhP"), expected);
+        assert_eq!(
+            decode_base64("mQQFEvxTlMN8AAT//AQMrDZCAM3LuDNMiE8="),
+            expected
+        );
+
+        assert_eq!(decode_base64(""), Err(DecodeError::NoData));
+        assert_eq!(decode_base64("mQQ="), Err(DecodeError::NoData));
+        assert!(matches!(
+            decode_base64("not base64!"),
+            Err(DecodeError::InvalidBase64(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_validated() {
+        let ble_data = "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+        assert_eq!(decode_validated(ble_data), decode(ble_data));
+
+        // Maximum vector decodes, but 163.835°C is not plausible
+        let ble_data = "18FFFF9904057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F";
+        assert!(decode(ble_data).is_ok());
+        assert!(matches!(
+            decode_validated(ble_data),
+            Err(DecodeError::ValidationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_all_concatenated_advertisements() {
+        let capture = concat!(
+            "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "020106",
+            "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+            "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        );
+        let results = decode_all(capture);
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Ok(RuuviData::V5(_))));
+        assert!(matches!(results[1], Ok(RuuviData::V6(_))));
+        assert!(matches!(results[2], Ok(RuuviData::E1(_))));
+    }
+
+    #[test]
+    fn test_decode_all_without_ad_header() {
+        // Same vectors as `test_decoding_ruuvi_data`, whose headers are not valid AD lengths
+        let capture = concat!(
+            "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "14FFFF990406170C5668C79E007000C90501D9FFCD004C884F",
+        );
+        let results = decode_all(capture);
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Ok(RuuviData::V5(_))));
+        assert!(matches!(results[1], Ok(RuuviData::V6(_))));
+    }
+
+    #[test]
+    fn test_decode_scan_buffer() {
+        let v5 =
+            hex::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let e1 = hex::decode(
+            "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        )
+        .unwrap();
+        let buffer = [v5.as_slice(), &e1, &v5].concat();
+
+        let results = decode_scan_buffer(&buffer);
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Ok(RuuviData::V5(_))));
+        assert!(matches!(results[1], Ok(RuuviData::E1(_))));
+        assert_eq!(results[2], results[0]);
+        assert_eq!(
+            decode_scan_buffer(&buffer),
+            decode_all(&hex::encode(&buffer))
+        );
+    }
+
+    #[rstest]
+    #[case::mid_payload(12, DecodeError::InvalidLength(String::new()))]
+    #[case::mid_mac(30, DecodeError::InvalidLength(String::new()))]
+    #[case::after_manufacturer_id(7, DecodeError::NoData)]
+    fn test_decode_scan_buffer_partial_frame(#[case] cut: usize, #[case] expected: DecodeError) {
+        let frame =
+            hex::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let buffer = [frame.as_slice(), &frame[..cut]].concat();
+
+        let results = decode_scan_buffer(&buffer);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert_eq!(
+            std::mem::discriminant(results[1].as_ref().unwrap_err()),
+            std::mem::discriminant(&expected)
+        );
+    }
+
+    #[test]
+    fn test_decode_all_errors() {
+        assert_eq!(decode_all(""), vec![Err(DecodeError::NoData)]);
+        assert!(decode_all("020106030316910255AA").is_empty());
+
+        let results = decode_all("0201060AFF99046300000000000000");
+        assert_eq!(results, vec![Err(DecodeError::UnsupportedFormat(99))]);
+
+        // Truncated segment is reported, not skipped
+        let results = decode_all("0201061BFF99040512FC5394C37C0004");
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(DecodeError::InvalidLength(_))));
+    }
+
+    #[test]
+    fn test_extract_ruuvi_from_ble() {
+        assert_eq!(
+            extract_ruuvi_from_ble(
+                "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F"
+            )
+            .as_deref(),
+            Some("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+        );
+        assert_eq!(
+            extract_ruuvi_from_ble("14FFFF990406170C5668C79E007000C90501D9FFCD004C884F").as_deref(),
+            Some("06170C5668C79E007000C90501D9FFCD004C884F")
+        );
+        // Manufacturer data after a local name structure containing 0x99 0x04
+        assert_eq!(
+            extract_ruuvi_from_ble(concat!(
+                "020106",
+                "050999040102",
+                "17FF990406170C5668C79E007000C90501D9FFCD004C884F"
+            ))
+            .as_deref(),
+            Some("06170C5668C79E007000C90501D9FFCD004C884F")
+        );
+        assert_eq!(extract_ruuvi_from_ble("020106030316910255AA"), None);
+        assert_eq!(extract_ruuvi_from_ble("0201069904"), None);
+        assert_eq!(extract_ruuvi_from_ble("not hex"), None);
+    }
+
+    #[test]
+    fn test_reassemble_extended() {
+        let payload = reassemble_extended(&[
+            "02010610FF99040512FC5394C37C0004FFFC040C",
+            "0EFF9904AC364200CDCBB8334C884F",
+        ])
+        .unwrap();
+        assert_eq!(payload, "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F");
+        assert_eq!(
+            decode_auto(&payload),
+            decode("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+        );
+
+        // Out of order fragments no longer decode
+        let swapped = reassemble_extended(&[
+            "0EFF9904AC364200CDCBB8334C884F",
+            "02010610FF99040512FC5394C37C0004FFFC040C",
+        ])
+        .unwrap();
+        assert!(decode_auto(&swapped).is_err());
+
+        assert_eq!(reassemble_extended(&[]), None);
+        assert_eq!(
+            reassemble_extended(&["02010610FF99040512FC5394C37C0004FFFC040C", "020106"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_manufacturer_payload() {
+        let ble_data = concat!(
+            "020106",
+            "07FF3412DEADBEEF",
+            "1BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F"
+        );
+
+        assert_eq!(
+            extract_manufacturer_payload(ble_data, 0x1234).as_deref(),
+            Some("DEADBEEF")
+        );
+        // Company IDs are little-endian on air, 0x3412 is not present
+        assert_eq!(extract_manufacturer_payload(ble_data, 0x3412), None);
+        assert_eq!(
+            extract_manufacturer_payload(ble_data, ble::RUUVI_COMPANY_ID),
+            extract_ruuvi_from_ble(ble_data)
+        );
+        // No scan fallback for other company IDs
+        assert_eq!(
+            extract_manufacturer_payload("FFFF3412DEADBEEF", 0x1234),
+            None
+        );
+        assert_eq!(extract_manufacturer_payload("not hex", 0x1234), None);
+    }
+
+    #[test]
+    fn test_decode_stream() {
+        let lines = [
+            "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "",
+            "   ",
+            "14FFFF990406170C5668C79E007000C90501D9FFCD004C884F",
+            "1BFFFF049963000000000000000000000000000000000000000000000000",
+        ];
+        let results: Vec<_> = decode_stream(lines.iter().map(ToString::to_string)).collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0].1, Ok(RuuviData::V5(_))));
+        assert_eq!(results[1].0, lines[3]);
+        assert!(matches!(results[1].1, Ok(RuuviData::V6(_))));
+        assert_eq!(results[2].0, lines[4]);
+        assert_eq!(results[2].1, Err(DecodeError::UnsupportedFormat(99)));
+    }
+
+    #[test]
+    fn test_decode_stream_is_lazy() {
+        let mut stream = decode_stream(std::iter::repeat_with(|| {
+            "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F".to_string()
+        }));
+        // An endless input must not be collected up front
+        assert!(stream.next().is_some_and(|(_, result)| result.is_ok()));
+        assert!(stream.next().is_some());
+    }
+
     #[test]
     fn test_decoding_ruuvi_data() {
         let ble_data = "18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
@@ -235,4 +1471,62 @@ mod tests {
             _ => panic!("Unexpected data format"),
         }
     }
+
+    /// Payloads of every supported length, led by a supported format byte half the time
+    fn payload_bytes() -> impl Strategy<Value = Vec<u8>> {
+        let lengths = prop_oneof![
+            Just(0usize),
+            Just(1),
+            Just(v5::PAYLOAD_LENGTH),
+            Just(v5::PAYLOAD_WITH_MAC_LENGTH),
+            Just(v6::PAYLOAD_WITH_MAC_LENGTH),
+            Just(e1::PAYLOAD_WITH_MAC_LENGTH),
+            Just(e1::PAYLOAD_WITH_MAC_AND_FLAGS_LENGTH),
+            0usize..64,
+        ];
+        let format = prop_oneof![Just(0x05u8), Just(0x06), Just(0xE1), any::<u8>()];
+
+        (lengths, format).prop_flat_map(|(len, format)| {
+            proptest::collection::vec(any::<u8>(), len).prop_map(move |mut bytes| {
+                if let Some(first) = bytes.first_mut() {
+                    *first = format;
+                }
+                bytes
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_decode_bytes_never_panics(bytes in payload_bytes()) {
+            let _ = decode_bytes(&bytes);
+            let _ = RuuviData::try_from(bytes.as_slice());
+            let _ = v5::decode(&bytes);
+            let _ = v5::decode_partial(&bytes);
+            let _ = v6::decode(&bytes);
+            let _ = e1::decode(&bytes);
+            for format in DataFormat::all() {
+                let _ = decode_with_format(format, &bytes);
+            }
+        }
+
+        #[test]
+        fn prop_decode_hex_never_panics(bytes in payload_bytes(), prefix in proptest::collection::vec(any::<u8>(), 0..8)) {
+            let mut advertisement = prefix;
+            advertisement.extend_from_slice(&[0xFF, 0x99, 0x04]);
+            advertisement.extend_from_slice(&bytes);
+            let ble_data = hex::encode(&advertisement);
+
+            let _ = decode(&ble_data);
+            let _ = decode_all(&ble_data);
+            let _ = extract_ruuvi_from_ble(&ble_data);
+        }
+
+        #[test]
+        fn prop_decode_arbitrary_text_never_panics(text in ".{0,80}") {
+            let _ = decode(&text);
+            let _ = decode_all(&text);
+            let _ = extract_ruuvi_from_ble(&text);
+        }
+    }
 }
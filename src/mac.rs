@@ -0,0 +1,401 @@
+//! Typed MAC addresses
+//!
+//! The format structs keep `mac_address` as a lowercase hex `String` for
+//! compatibility; their `mac()` accessors parse it into a [`MacAddress`].
+//! Data Format 6 only carries the lower 3 bytes, see [`ShortMacAddress`].
+
+use crate::error::DecodeError;
+use crate::ruuvi_data::DataFormat;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// MAC address of `N` bytes, 6 unless stated otherwise
+///
+/// Displays and serializes in colon format, e.g. `cb:b8:33:4c:88:4f`.
+/// Parsing accepts bare hex or bytes separated by `:` or `-`, in either case.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::MacAddress;
+///
+/// let mac: MacAddress = "CB:B8:33:4C:88:4F".parse().unwrap();
+/// assert_eq!(mac, MacAddress([0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F]));
+/// assert_eq!(mac.to_string(), "cb:b8:33:4c:88:4f");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MacAddress<const N: usize = 6>(pub [u8; N]);
+
+/// The lower 3 bytes of a MAC address, as carried by Data Format 6
+pub type ShortMacAddress = MacAddress<3>;
+
+impl<const N: usize> MacAddress<N> {
+    /// Whether every byte is `0xFF`, the sentinel for an unknown address
+    #[must_use]
+    pub fn is_broadcast(&self) -> bool {
+        self.0.iter().all(|&b| b == 0xFF)
+    }
+
+    /// The address bytes, most significant first
+    #[must_use]
+    pub fn bytes(&self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for MacAddress<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> FromStr for MacAddress<N> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: String = s.chars().filter(|c| *c != ':' && *c != '-').collect();
+        hex::decode(&digits)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Self)
+            .ok_or_else(|| DecodeError::InvalidMacAddress(s.to_string()))
+    }
+}
+
+impl<const N: usize> TryFrom<String> for MacAddress<N> {
+    type Error = DecodeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<const N: usize> From<MacAddress<N>> for String {
+    fn from(mac: MacAddress<N>) -> Self {
+        mac.to_string()
+    }
+}
+
+impl<const N: usize> fmt::Display for MacAddress<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a 6-byte MAC address as lowercase hex into `buf`, without allocating
+///
+/// The decoders store `mac_address` as an owned `String`; this lets a hot
+/// loop format the address of a raw payload into a reused buffer instead.
+///
+/// # Arguments
+///
+/// * `bytes` - The 6 address bytes, most significant first
+/// * `buf` - Buffer receiving the 12 hex digits
+///
+/// # Returns
+///
+/// The hex digits, borrowed from `buf`
+///
+/// # Panics
+///
+/// If `bytes` is not exactly 6 bytes long
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::mac::mac_hex_into;
+///
+/// let mut buf = [0u8; 12];
+/// let mac = mac_hex_into(&[0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F], &mut buf);
+/// assert_eq!(mac, "cbb8334c884f");
+/// ```
+pub fn mac_hex_into<'a>(bytes: &[u8], buf: &'a mut [u8; 12]) -> &'a str {
+    assert_eq!(bytes.len(), 6, "MAC address must be 6 bytes");
+    hex::encode_to_slice(bytes, buf).expect("12 digits fit 6 bytes");
+    std::str::from_utf8(buf).expect("hex digits are ASCII")
+}
+
+/// 64-bit FNV-1a offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// 64-bit FNV-1a prime
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Replace address bytes with the leading bytes of their FNV-1a hash
+///
+/// The same address always maps to the same pseudonym, so readings of one
+/// tag can still be grouped. This hides the address from casual readers but
+/// is not cryptographic: the address space is small enough to brute force.
+/// The all-`0xFF` "not available" sentinel is kept.
+fn pseudonymize(bytes: &mut [u8]) {
+    if bytes.iter().all(|&b| b == 0xFF) {
+        return;
+    }
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    });
+    let len = bytes.len();
+    bytes.copy_from_slice(&hash.to_be_bytes()[..len]);
+}
+
+/// Pseudonymize a lowercase hex `mac_address` field in place
+///
+/// Placeholders that are not hex, such as `invalid`, are left unchanged.
+pub(crate) fn anonymize_mac_address(mac_address: &mut String) {
+    if let Ok(mut bytes) = hex::decode(mac_address.as_str()) {
+        pseudonymize(&mut bytes);
+        *mac_address = hex::encode(bytes);
+    }
+}
+
+/// Pseudonymize the MAC address of a raw payload hex string
+///
+/// Sensor bytes are copied unchanged; the MAC address bytes are replaced the
+/// same way as [`crate::RuuviData::anonymize_mac`] does after decoding, so
+/// both paths yield the same reading. Separators are stripped like
+/// [`crate::decode`] does and the result is uppercase hex.
+///
+/// # Arguments
+///
+/// * `hex` - Hex string of the Ruuvi payload starting with the format byte
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::mac::anonymize_mac_in_hex;
+///
+/// let payload = "0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F";
+/// let anonymized = anonymize_mac_in_hex(payload).unwrap();
+/// assert_eq!(anonymized[..36], payload[..36]);
+/// assert_ne!(anonymized[36..], payload[36..]);
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::NoData` - Empty hex string
+/// * `DecodeError::InvalidHex` - Invalid hex string
+/// * `DecodeError::UnsupportedFormat` - Unsupported data format
+/// * `DecodeError::InvalidLength` - Invalid payload length for the format
+pub fn anonymize_mac_in_hex(hex: &str) -> crate::Result<String> {
+    let mut bytes = crate::hex_to_bytes(&crate::clean_hex(hex)?)?;
+    let format = DataFormat::detect(&bytes).ok_or(DecodeError::UnsupportedFormat(bytes[0]))?;
+    format.validate_length(&bytes)?;
+
+    pseudonymize(&mut bytes[format.payload_length()..format.payload_with_mac_length()]);
+    Ok(hex::encode_upper(bytes))
+}
+
+/// Serialize a bare hex MAC address as uppercase bytes separated by colons
+///
+/// Meant for `#[serde(serialize_with = "...")]` on `mac_address` strings:
+/// `cbb8334c884f` becomes `CB:B8:33:4C:88:4F`. Placeholders that are not hex,
+/// such as `invalid`, are written unchanged. The `mac-colons` feature applies
+/// this to the format structs.
+///
+/// # Errors
+///
+/// Only those of the serializer
+pub fn serialize_colon_uppercase<S: Serializer>(
+    mac_address: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if mac_address.is_empty()
+        || !mac_address.len().is_multiple_of(2)
+        || !mac_address.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return serializer.serialize_str(mac_address);
+    }
+
+    let bytes = mac_address.as_bytes().chunks(2);
+    let pairs: Vec<String> = bytes
+        .map(|pair| String::from_utf8_lossy(pair).to_ascii_uppercase())
+        .collect();
+    serializer.serialize_str(&pairs.join(":"))
+}
+
+/// Deserialize a MAC address back to bare lowercase hex
+///
+/// Counterpart of [`serialize_colon_uppercase`]. Accepts bare hex or bytes
+/// separated by `:` or `-`, in either case; anything else, such as a
+/// placeholder, is kept as is.
+///
+/// # Errors
+///
+/// If the value is not a string
+pub fn deserialize_bare<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let mac_address = String::deserialize(deserializer)?;
+    let digits: String = mac_address
+        .chars()
+        .filter(|c| *c != ':' && *c != '-')
+        .collect();
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(digits.to_ascii_lowercase())
+    } else {
+        Ok(mac_address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const MAC: MacAddress = MacAddress([0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F]);
+
+    #[rstest]
+    #[case("cbb8334c884f")]
+    #[case("CBB8334C884F")]
+    #[case("cb:b8:33:4c:88:4f")]
+    #[case("CB-B8-33-4C-88-4F")]
+    fn test_parse(#[case] input: &str) {
+        assert_eq!(input.parse::<MacAddress>(), Ok(MAC));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("cbb8334c88")]
+    #[case("cbb8334c884f00")]
+    #[case("invalid")]
+    fn test_parse_errors(#[case] input: &str) {
+        assert_eq!(
+            input.parse::<MacAddress>(),
+            Err(DecodeError::InvalidMacAddress(input.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_mac_hex_into() {
+        let mut buf = [0u8; 12];
+        assert_eq!(mac_hex_into(&MAC.0, &mut buf), "cbb8334c884f");
+        assert_eq!(mac_hex_into(&[0xFF; 6], &mut buf), "ffffffffffff");
+        assert_eq!(mac_hex_into(&MAC.0, &mut buf), hex::encode(MAC.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "MAC address must be 6 bytes")]
+    fn test_mac_hex_into_wrong_length() {
+        mac_hex_into(&[0xCB, 0xB8, 0x33], &mut [0u8; 12]);
+    }
+
+    #[rstest]
+    #[case::v5("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::v6("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case::e1("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    fn test_anonymize_mac_in_hex(#[case] payload: &str) {
+        let decode = |hex: &str| crate::decode_bytes(&hex::decode(hex).unwrap()).unwrap();
+        let anonymized = anonymize_mac_in_hex(payload).unwrap();
+        assert_eq!(anonymize_mac_in_hex(payload).unwrap(), anonymized);
+
+        let original = decode(payload);
+        let scrubbed = decode(&anonymized);
+        assert!(original.same_measurement(&scrubbed));
+        assert_ne!(original, scrubbed);
+
+        let mut after_decode = original;
+        after_decode.anonymize_mac();
+        assert_eq!(after_decode, scrubbed);
+    }
+
+    #[test]
+    fn test_anonymize_mac_in_hex_errors() {
+        assert_eq!(anonymize_mac_in_hex(""), Err(DecodeError::NoData));
+        assert_eq!(
+            anonymize_mac_in_hex("0312FC"),
+            Err(DecodeError::UnsupportedFormat(3))
+        );
+        assert!(matches!(
+            anonymize_mac_in_hex("0512FC"),
+            Err(DecodeError::InvalidLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_anonymize_keeps_sentinels() {
+        let mut placeholder = String::from("invalid");
+        anonymize_mac_address(&mut placeholder);
+        assert_eq!(placeholder, "invalid");
+
+        let mut unavailable = String::from("ffffffffffff");
+        anonymize_mac_address(&mut unavailable);
+        assert_eq!(unavailable, "ffffffffffff");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(MAC.to_string(), "cb:b8:33:4c:88:4f");
+        assert_eq!(
+            ShortMacAddress::from([0x4C, 0x88, 0x4F]).to_string(),
+            "4c:88:4f"
+        );
+    }
+
+    #[test]
+    fn test_short_mac_address() {
+        assert_eq!(
+            "4c884f".parse::<ShortMacAddress>(),
+            Ok(MacAddress([0x4C, 0x88, 0x4F]))
+        );
+        assert!("cbb8334c884f".parse::<ShortMacAddress>().is_err());
+    }
+
+    #[test]
+    fn test_is_broadcast() {
+        assert!(!MAC.is_broadcast());
+        assert!(MacAddress([0xFF; 6]).is_broadcast());
+        assert!(ShortMacAddress::from([0xFF; 3]).is_broadcast());
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&MAC).unwrap();
+        assert_eq!(json, r#""cb:b8:33:4c:88:4f""#);
+        assert_eq!(serde_json::from_str::<MacAddress>(&json).unwrap(), MAC);
+        assert_eq!(
+            serde_json::from_str::<MacAddress>(r#""cbb8334c884f""#).unwrap(),
+            MAC
+        );
+        assert!(serde_json::from_str::<MacAddress>(r#""4c884f""#).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Tagged {
+        #[serde(
+            serialize_with = "serialize_colon_uppercase",
+            deserialize_with = "deserialize_bare"
+        )]
+        mac_address: String,
+    }
+
+    #[rstest]
+    #[case("cbb8334c884f", r#"{"mac_address":"CB:B8:33:4C:88:4F"}"#)]
+    #[case("4c884f", r#"{"mac_address":"4C:88:4F"}"#)]
+    #[case("invalid", r#"{"mac_address":"invalid"}"#)]
+    #[case("", r#"{"mac_address":""}"#)]
+    fn test_colon_uppercase_round_trip(#[case] mac_address: &str, #[case] json: &str) {
+        let tagged = Tagged {
+            mac_address: mac_address.into(),
+        };
+        assert_eq!(serde_json::to_string(&tagged).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Tagged>(json).unwrap(), tagged);
+    }
+
+    #[test]
+    fn test_deserialize_bare_accepts_any_form() {
+        for json in [
+            r#"{"mac_address":"cbb8334c884f"}"#,
+            r#"{"mac_address":"cb-b8-33-4c-88-4f"}"#,
+        ] {
+            assert_eq!(
+                serde_json::from_str::<Tagged>(json).unwrap().mac_address,
+                "cbb8334c884f"
+            );
+        }
+    }
+}
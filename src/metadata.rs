@@ -0,0 +1,182 @@
+//! Unit, resolution and range of every decoded field
+//!
+//! Surfaces the scale factors and limits the decoders apply, so callers can
+//! label, format and validate readings without hard-coding them.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::ruuvi_data::DataFormat;
+use crate::v6;
+
+/// Physical description of one decoded field
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FieldMeta {
+    /// Unit of the decoded value, empty for counters and indexes
+    pub unit: &'static str,
+    /// Step between adjacent raw values, in `unit`
+    ///
+    /// The V6 luminosity is logarithmic; its resolution is the smallest step,
+    /// between codes 0 and 1, and grows with the value.
+    pub resolution: f64,
+    /// Lowest value in the format specification
+    pub min: f64,
+    /// Highest value in the format specification
+    ///
+    /// Formats 6 and E1 specify humidity up to 100%, although the raw field
+    /// can carry up to 163.835% like format 5.
+    pub max: f64,
+}
+
+const fn meta(unit: &'static str, resolution: f64, min: f64, max: f64) -> FieldMeta {
+    FieldMeta {
+        unit,
+        resolution,
+        min,
+        max,
+    }
+}
+
+const TEMPERATURE: FieldMeta = meta("°C", 0.005, -163.835, 163.835);
+const HUMIDITY: FieldMeta = meta("%", 0.0025, 0.0, 100.0);
+const PRESSURE: FieldMeta = meta("hPa", 0.01, 500.0, 1155.34);
+const ACCELERATION: FieldMeta = meta("mg", 1.0, -32767.0, 32767.0);
+const PARTICULATE_MATTER: FieldMeta = meta("μg/m³", 0.1, 0.0, 1000.0);
+const CO2: FieldMeta = meta("ppm", 1.0, 0.0, 40000.0);
+const GAS_INDEX: FieldMeta = meta("", 1.0, 1.0, 500.0);
+
+/// Metadata of every sensor field of `format`, keyed by field name
+///
+/// Field names match `readings()` on the format specific structs. The MAC
+/// address, flags and reserved bytes are not included.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::DataFormat;
+/// use ruuvi_decoders::metadata::field_metadata;
+///
+/// let temperature = field_metadata(DataFormat::V5)["temperature"];
+/// assert_eq!(temperature.unit, "°C");
+/// assert_eq!(temperature.resolution, 0.005);
+/// ```
+#[must_use]
+pub fn field_metadata(format: DataFormat) -> BTreeMap<&'static str, FieldMeta> {
+    let mut fields = BTreeMap::from([
+        ("temperature", TEMPERATURE),
+        ("humidity", HUMIDITY),
+        ("pressure", PRESSURE),
+    ]);
+
+    match format {
+        DataFormat::V5 => fields.extend([
+            ("humidity", meta("%", 0.0025, 0.0, 163.835)),
+            ("acceleration_x", ACCELERATION),
+            ("acceleration_y", ACCELERATION),
+            ("acceleration_z", ACCELERATION),
+            ("battery_voltage", meta("mV", 1.0, 1600.0, 3646.0)),
+            ("tx_power", meta("dBm", 2.0, -40.0, 20.0)),
+            ("movement_counter", meta("", 1.0, 0.0, 254.0)),
+            ("measurement_sequence", meta("", 1.0, 0.0, 65534.0)),
+        ]),
+        DataFormat::V6 => fields.extend([
+            ("pm2_5", PARTICULATE_MATTER),
+            ("co2", CO2),
+            ("voc_index", GAS_INDEX),
+            ("nox_index", GAS_INDEX),
+            (
+                "luminosity",
+                meta(
+                    "lx",
+                    v6::LUMINOSITY_DELTA.exp_m1(),
+                    0.0,
+                    v6::LUMINOSITY_MAX_VALUE,
+                ),
+            ),
+            ("measurement_sequence", meta("", 1.0, 0.0, 255.0)),
+        ]),
+        DataFormat::E1 => fields.extend([
+            ("pm1_0", PARTICULATE_MATTER),
+            ("pm2_5", PARTICULATE_MATTER),
+            ("pm4_0", PARTICULATE_MATTER),
+            ("pm10_0", PARTICULATE_MATTER),
+            ("co2", CO2),
+            ("voc_index", GAS_INDEX),
+            ("nox_index", GAS_INDEX),
+            ("luminosity", meta("lx", 0.01, 0.0, 144_284.0)),
+            ("measurement_sequence", meta("", 1.0, 0.0, 16_777_214.0)),
+        ]),
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadingValue, RuuviData};
+    use rstest::rstest;
+
+    fn numeric(value: &ReadingValue) -> Option<f64> {
+        match *value {
+            ReadingValue::F64(value) => Some(value),
+            ReadingValue::I8(value) => Some(value.into()),
+            ReadingValue::I16(value) => Some(value.into()),
+            ReadingValue::U8(value) => Some(value.into()),
+            ReadingValue::U16(value) => Some(value.into()),
+            ReadingValue::U32(value) => Some(value.into()),
+            ReadingValue::String(_) => None,
+        }
+    }
+
+    #[rstest]
+    #[case::v5_max("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F")]
+    #[case::v5_min("058001000000008001800180010000000000CBB8334C884F")]
+    #[case::v6_max("067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F")]
+    #[case::v6_min("06800100000000000000000000000000004C884F")]
+    #[case::e1_max(
+        "E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F"
+    )]
+    fn test_readings_within_range(#[case] hex_str: &str) {
+        let data = RuuviData::decode(&hex::decode(hex_str).unwrap()).unwrap();
+        let metadata = field_metadata(data.format());
+
+        for (name, value) in data.readings() {
+            let Some(meta) = metadata.get(name) else {
+                continue;
+            };
+            let value = numeric(&value).unwrap();
+            assert!(
+                (meta.min..=meta.max).contains(&value),
+                "{name} {value} outside {}..={}",
+                meta.min,
+                meta.max
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::v5("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F")]
+    #[case::v6("067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F")]
+    #[case::e1("E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F")]
+    fn test_max_vector_reaches_max(#[case] hex_str: &str) {
+        let data = RuuviData::decode(&hex::decode(hex_str).unwrap()).unwrap();
+        let readings: BTreeMap<_, _> = data.readings().collect();
+
+        for (name, meta) in field_metadata(data.format()) {
+            let value = numeric(&readings[name]).unwrap();
+            assert!(
+                (value - meta.max).abs() < 1e-9,
+                "{name} {value} != {}",
+                meta.max
+            );
+        }
+    }
+
+    #[test]
+    fn test_v6_luminosity_resolution() {
+        let luminosity = field_metadata(DataFormat::V6)["luminosity"];
+        assert!((luminosity.resolution - 0.0446).abs() < 1e-4);
+    }
+}
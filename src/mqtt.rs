@@ -0,0 +1,217 @@
+//! Ruuvi Gateway MQTT source
+//!
+//! A Ruuvi Gateway publishes one JSON [`RuuviGatewayEvent`] per advertisement,
+//! by default on `ruuvi/<gateway mac>/<sensor mac>`. [`RuuviMqtt`] subscribes
+//! to those topics and decodes every event with the regular decode path.
+//! Other gateway topics, such as `ruuvi/<gateway mac>/gw_status`, are skipped.
+
+use crate::ruuvi_data::RuuviGatewayEvent;
+use crate::{MacAddress, Result, RuuviData};
+use rumqttc::{AsyncClient, Event, EventLoop, Incoming};
+use thiserror::Error;
+
+pub use rumqttc::{MqttOptions, QoS};
+
+/// Capacity of the request channel between the client and its event loop
+const REQUEST_CHANNEL_CAPACITY: usize = 16;
+
+/// Errors from the MQTT transport or from malformed gateway messages
+#[derive(Error, Debug)]
+pub enum MqttError {
+    /// The client could not queue a request, e.g. the subscription
+    #[error("MQTT client error: {0}")]
+    Client(#[from] rumqttc::ClientError),
+
+    /// The connection to the broker failed
+    #[error("MQTT connection error: {0}")]
+    Connection(Box<rumqttc::ConnectionError>),
+
+    /// The message payload is not a gateway event
+    #[error("Invalid gateway event on {topic}: {source}")]
+    Payload {
+        topic: String,
+        source: serde_json::Error,
+    },
+}
+
+impl From<rumqttc::ConnectionError> for MqttError {
+    fn from(err: rumqttc::ConnectionError) -> Self {
+        Self::Connection(Box::new(err))
+    }
+}
+
+/// A gateway event received over MQTT, together with its decoded data
+#[derive(Debug, Clone)]
+pub struct GatewayMessage {
+    /// Topic the event was published on
+    pub topic: String,
+    /// Gateway event as published, including its metadata
+    pub event: RuuviGatewayEvent,
+    /// Result of decoding the event's `data`
+    pub decoded: Result<RuuviData>,
+}
+
+/// Whether `topic` carries sensor events, i.e. its last level is a MAC address
+///
+/// Holds for the gateway's `<prefix>/<gateway mac>/<sensor mac>` event topics
+/// but not for status topics such as `ruuvi/<gateway mac>/gw_status`.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::mqtt::is_event_topic;
+///
+/// assert!(is_event_topic("ruuvi/C8:25:2D:8E:9C:2C/CB:B8:33:4C:88:4F"));
+/// assert!(!is_event_topic("ruuvi/C8:25:2D:8E:9C:2C/gw_status"));
+/// ```
+#[must_use]
+pub fn is_event_topic(topic: &str) -> bool {
+    topic
+        .rsplit('/')
+        .next()
+        .is_some_and(|level| level.parse::<MacAddress>().is_ok())
+}
+
+/// Parse one gateway MQTT message and decode the advertisement it carries
+///
+/// Only a payload that is not a gateway event is an error; a well-formed event
+/// whose `data` fails to decode is returned with the error in
+/// [`GatewayMessage::decoded`].
+///
+/// # Errors
+///
+/// * `MqttError::Payload` - The payload is not a gateway event
+pub fn parse_message(
+    topic: &str,
+    payload: &[u8],
+) -> std::result::Result<GatewayMessage, MqttError> {
+    let event: RuuviGatewayEvent =
+        serde_json::from_slice(payload).map_err(|source| MqttError::Payload {
+            topic: topic.to_string(),
+            source,
+        })?;
+    let decoded = event.decoded();
+
+    Ok(GatewayMessage {
+        topic: topic.to_string(),
+        event,
+        decoded,
+    })
+}
+
+/// Async subscription to the events of one or more Ruuvi Gateways
+///
+/// Must be driven from a Tokio runtime.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ruuvi_decoders::mqtt::{MqttError, MqttOptions, RuuviMqtt};
+///
+/// # async fn run() -> Result<(), MqttError> {
+/// let options = MqttOptions::new("ruuvi-scanner", "localhost", 1883);
+/// let mut mqtt = RuuviMqtt::subscribe(options, "ruuvi/#").await?;
+///
+/// loop {
+///     match mqtt.next().await {
+///         Ok(message) => println!("{}: {:?}", message.topic, message.decoded),
+///         // A malformed message does not end the subscription
+///         Err(MqttError::Payload { topic, source }) => eprintln!("{topic}: {source}"),
+///         Err(err) => return Err(err),
+///     }
+/// }
+/// # }
+/// ```
+pub struct RuuviMqtt {
+    // Kept alive so the event loop does not see the request channel close
+    _client: AsyncClient,
+    eventloop: EventLoop,
+}
+
+impl RuuviMqtt {
+    /// Connect to the broker and subscribe to `topic`
+    ///
+    /// The connection is established lazily by [`RuuviMqtt::next`].
+    ///
+    /// # Errors
+    ///
+    /// * `MqttError::Client` - The subscription could not be queued
+    pub async fn subscribe(
+        options: MqttOptions,
+        topic: &str,
+    ) -> std::result::Result<Self, MqttError> {
+        let (client, eventloop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+        client.subscribe(topic, QoS::AtMostOnce).await?;
+
+        Ok(Self {
+            _client: client,
+            eventloop,
+        })
+    }
+
+    /// Wait for the next published sensor event and decode it
+    ///
+    /// Protocol traffic such as acknowledgements and pings is handled
+    /// internally and not returned, and so are messages on topics that are
+    /// not event topics, see [`is_event_topic`].
+    ///
+    /// # Errors
+    ///
+    /// * `MqttError::Connection` - The connection to the broker failed
+    /// * `MqttError::Payload` - The payload is not a gateway event
+    pub async fn next(&mut self) -> std::result::Result<GatewayMessage, MqttError> {
+        loop {
+            if let Event::Incoming(Incoming::Publish(publish)) = self.eventloop.poll().await?
+                && is_event_topic(&publish.topic)
+            {
+                return parse_message(&publish.topic, &publish.payload);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodeError;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/ruuvi_gateway_mqtt.json");
+    const TOPIC: &str = "ruuvi/C8:25:2D:8E:9C:2C/CB:B8:33:4C:88:4F";
+
+    #[test]
+    fn test_parse_recorded_message() {
+        let message = parse_message(TOPIC, FIXTURE.as_bytes()).expect("Parsed message");
+
+        assert_eq!(message.topic, TOPIC);
+        assert_eq!(message.event.gw_mac, "C8:25:2D:8E:9C:2C");
+        assert_eq!(message.event.rssi, -62);
+        assert_eq!(message.decoded, crate::decode(&message.event.data));
+        assert!(matches!(message.decoded, Ok(RuuviData::V5(_))));
+    }
+
+    #[test]
+    fn test_parse_undecodable_data() {
+        let payload = FIXTURE.replace("99040512", "99040712");
+        let message = parse_message(TOPIC, payload.as_bytes()).expect("Parsed message");
+
+        assert_eq!(message.decoded, Err(DecodeError::UnsupportedFormat(0x07)));
+    }
+
+    #[test]
+    fn test_event_topics() {
+        assert!(is_event_topic(TOPIC));
+        assert!(is_event_topic("home/gw/cbb8334c884f"));
+        assert!(!is_event_topic("ruuvi/C8:25:2D:8E:9C:2C/gw_status"));
+        assert!(!is_event_topic("ruuvi/C8:25:2D:8E:9C:2C/CB:B8:33:4C:88"));
+        assert!(!is_event_topic(""));
+    }
+
+    #[test]
+    fn test_parse_invalid_payload() {
+        let err = parse_message("ruuvi/C8:25:2D:8E:9C:2C/gw_status", b"online").unwrap_err();
+
+        assert!(
+            matches!(err, MqttError::Payload { ref topic, .. } if topic.ends_with("gw_status"))
+        );
+    }
+}
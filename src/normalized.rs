@@ -0,0 +1,309 @@
+//! Format independent, flat view of decoded Ruuvi data
+//!
+//! Useful for storing readings from all data formats in a single table or
+//! time series measurement.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ruuvi_data::{DataFormat, RuuviData};
+
+/// One flat record covering the fields of every supported data format
+///
+/// Fields that the source format does not carry are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
+pub struct NormalizedReading {
+    /// Data format the reading was decoded from
+    pub format: DataFormat,
+    /// MAC address as lowercase hex string (3 bytes for V6, 6 bytes otherwise)
+    #[cfg_attr(
+        feature = "mac-colons",
+        serde(
+            serialize_with = "crate::mac::serialize_colon_uppercase",
+            deserialize_with = "crate::mac::deserialize_bare"
+        )
+    )]
+    pub mac_address: String,
+    /// Temperature in Celsius
+    pub temperature: Option<f64>,
+    /// Humidity in %
+    pub humidity: Option<f64>,
+    /// Pressure in hPa
+    pub pressure: Option<f64>,
+    /// Acceleration X-axis in mg (V5)
+    pub acceleration_x: Option<i16>,
+    /// Acceleration Y-axis in mg (V5)
+    pub acceleration_y: Option<i16>,
+    /// Acceleration Z-axis in mg (V5)
+    pub acceleration_z: Option<i16>,
+    /// Battery voltage in mV (V5)
+    pub battery_voltage: Option<u16>,
+    /// TX power in dBm (V5)
+    pub tx_power: Option<i8>,
+    /// Movement counter (V5)
+    pub movement_counter: Option<u8>,
+    /// Measurement sequence number, widened to fit every format
+    pub measurement_sequence: Option<u32>,
+    /// PM1.0 in μg/m³ (E1)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm1"))]
+    pub pm1_0: Option<f64>,
+    /// PM2.5 in μg/m³ (V6, E1)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm25"))]
+    pub pm2_5: Option<f64>,
+    /// PM4.0 in μg/m³ (E1)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm4"))]
+    pub pm4_0: Option<f64>,
+    /// PM10.0 in μg/m³ (E1)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm10"))]
+    pub pm10_0: Option<f64>,
+    /// CO2 concentration in ppm (V6, E1)
+    pub co2: Option<u16>,
+    /// VOC index (V6, E1)
+    pub voc_index: Option<u16>,
+    /// `NOx` index (V6, E1)
+    pub nox_index: Option<u16>,
+    /// Luminosity in Lux (V6, E1)
+    pub luminosity: Option<f64>,
+    /// Raw flags byte (V6, E1)
+    pub flags: Option<u8>,
+    /// Air quality score, when PM2.5 and CO2 are both available
+    pub air_quality: Option<f64>,
+}
+
+/// CSV columns, in the order of [`NormalizedReading::to_csv_row`]
+const CSV_COLUMNS: [&str; 22] = [
+    "format",
+    "mac_address",
+    "temperature",
+    "humidity",
+    "pressure",
+    "acceleration_x",
+    "acceleration_y",
+    "acceleration_z",
+    "battery_voltage",
+    "tx_power",
+    "movement_counter",
+    "measurement_sequence",
+    "pm1_0",
+    "pm2_5",
+    "pm4_0",
+    "pm10_0",
+    "co2",
+    "voc_index",
+    "nox_index",
+    "luminosity",
+    "flags",
+    "air_quality",
+];
+
+impl NormalizedReading {
+    /// CSV header line matching [`NormalizedReading::to_csv_row`], without a line break
+    #[must_use]
+    pub fn csv_header() -> String {
+        CSV_COLUMNS.join(",")
+    }
+
+    /// The reading as one CSV line, without a line break
+    ///
+    /// Columns follow [`NormalizedReading::csv_header`]; fields the format does
+    /// not carry are empty cells. No cell ever needs quoting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::{decode, NormalizedReading};
+    ///
+    /// let data = decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+    /// let row = NormalizedReading::from(data).to_csv_row();
+    /// assert!(row.starts_with("RAWv2,cbb8334c884f,24.3,53.49,1000.44,4,-4,1036,"));
+    /// ```
+    #[must_use]
+    pub fn to_csv_row(&self) -> String {
+        fn cell<T: ToString>(value: Option<T>) -> String {
+            value.map(|value| value.to_string()).unwrap_or_default()
+        }
+
+        [
+            self.format.to_string(),
+            self.mac_address.clone(),
+            cell(self.temperature),
+            cell(self.humidity),
+            cell(self.pressure),
+            cell(self.acceleration_x),
+            cell(self.acceleration_y),
+            cell(self.acceleration_z),
+            cell(self.battery_voltage),
+            cell(self.tx_power),
+            cell(self.movement_counter),
+            cell(self.measurement_sequence),
+            cell(self.pm1_0),
+            cell(self.pm2_5),
+            cell(self.pm4_0),
+            cell(self.pm10_0),
+            cell(self.co2),
+            cell(self.voc_index),
+            cell(self.nox_index),
+            cell(self.luminosity),
+            cell(self.flags),
+            cell(self.air_quality),
+        ]
+        .join(",")
+    }
+
+    /// Reading with only the format and MAC address set
+    fn empty(format: DataFormat, mac_address: String) -> Self {
+        Self {
+            format,
+            mac_address,
+            temperature: None,
+            humidity: None,
+            pressure: None,
+            acceleration_x: None,
+            acceleration_y: None,
+            acceleration_z: None,
+            battery_voltage: None,
+            tx_power: None,
+            movement_counter: None,
+            measurement_sequence: None,
+            pm1_0: None,
+            pm2_5: None,
+            pm4_0: None,
+            pm10_0: None,
+            co2: None,
+            voc_index: None,
+            nox_index: None,
+            luminosity: None,
+            flags: None,
+            air_quality: None,
+        }
+    }
+}
+
+impl From<RuuviData> for NormalizedReading {
+    fn from(data: RuuviData) -> Self {
+        let air_quality = data.calculate_air_quality();
+
+        match data {
+            RuuviData::V5(v5) => Self {
+                temperature: v5.temperature,
+                humidity: v5.humidity,
+                pressure: v5.pressure,
+                acceleration_x: v5.acceleration_x,
+                acceleration_y: v5.acceleration_y,
+                acceleration_z: v5.acceleration_z,
+                battery_voltage: v5.battery_voltage,
+                tx_power: v5.tx_power,
+                movement_counter: v5.movement_counter,
+                measurement_sequence: v5.measurement_sequence.map(u32::from),
+                air_quality,
+                ..Self::empty(DataFormat::V5, v5.mac_address)
+            },
+            RuuviData::V6(v6) => Self {
+                temperature: v6.temperature,
+                humidity: v6.humidity,
+                pressure: v6.pressure,
+                measurement_sequence: v6.measurement_sequence.map(u32::from),
+                pm2_5: v6.pm2_5,
+                co2: v6.co2,
+                voc_index: v6.voc_index,
+                nox_index: v6.nox_index,
+                luminosity: v6.luminosity,
+                flags: Some(v6.flags),
+                air_quality,
+                ..Self::empty(DataFormat::V6, v6.mac_address)
+            },
+            RuuviData::E1(e1) => Self {
+                temperature: e1.temperature,
+                humidity: e1.humidity,
+                pressure: e1.pressure,
+                measurement_sequence: e1.measurement_sequence,
+                pm1_0: e1.pm1_0,
+                pm2_5: e1.pm2_5,
+                pm4_0: e1.pm4_0,
+                pm10_0: e1.pm10_0,
+                co2: e1.co2,
+                voc_index: e1.voc_index,
+                nox_index: e1.nox_index,
+                luminosity: e1.luminosity,
+                flags: Some(e1.flags),
+                air_quality,
+                ..Self::empty(DataFormat::E1, e1.mac_address)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn reading(ble_data: &str) -> NormalizedReading {
+        crate::decode(ble_data).unwrap().into()
+    }
+
+    #[test]
+    fn from_v5() {
+        let reading = reading("18FFFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F");
+
+        assert_eq!(reading.format, DataFormat::V5);
+        assert_eq!(reading.mac_address, "cbb8334c884f");
+        assert_eq!(reading.temperature, Some(24.3));
+        assert_eq!(reading.acceleration_z, Some(1036));
+        assert_eq!(reading.measurement_sequence, Some(205));
+        assert_eq!(reading.pm2_5, None);
+        assert_eq!(reading.flags, None);
+        assert_eq!(reading.air_quality, None);
+    }
+
+    #[test]
+    fn from_v6() {
+        let reading = reading("14FFFF990406170C5668C79E007000C90501D9FFCD004C884F");
+
+        assert_eq!(reading.format, DataFormat::V6);
+        assert_eq!(reading.mac_address, "4c884f");
+        assert_eq!(reading.co2, Some(201));
+        assert_eq!(reading.measurement_sequence, Some(205));
+        assert_eq!(reading.flags, Some(0));
+        assert_eq!(reading.acceleration_x, None);
+        assert!(reading.air_quality.is_some());
+    }
+
+    #[test]
+    fn from_e1() {
+        let reading = reading(
+            "2BFFFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        );
+
+        assert_eq!(reading.format, DataFormat::E1);
+        assert_eq!(reading.pm10_0, Some(455.400_000_000_000_03));
+        assert_eq!(reading.measurement_sequence, Some(14_601_710));
+        assert_eq!(reading.battery_voltage, None);
+        assert!(reading.air_quality.is_some());
+    }
+
+    #[rstest]
+    #[case("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("14FFFF990406170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case(
+        "2BFFFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+    )]
+    fn csv_row_matches_header(#[case] ble_data: &str) {
+        let header = NormalizedReading::csv_header();
+        let row = reading(ble_data).to_csv_row();
+        assert_eq!(row.split(',').count(), header.split(',').count());
+    }
+
+    #[test]
+    fn csv_row_v6() {
+        let reading = reading("14FFFF990406170C5668C79E007000C90501D9FFCD004C884F");
+        let row = reading.to_csv_row();
+        let cells: Vec<_> = row.split(',').collect();
+
+        assert_eq!(cells[..3], ["RAWv3", "4c884f", "29.5"]);
+        assert_eq!(cells[5..11], ["", "", "", "", "", ""]); // V5 only fields
+        assert_eq!(cells[16], "201");
+        assert_eq!(cells[20], "0");
+    }
+}
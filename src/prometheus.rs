@@ -0,0 +1,102 @@
+//! Prometheus text exposition format
+//!
+//! See [`RuuviData::to_prometheus`]. Only sample lines are written, without
+//! `# HELP` or `# TYPE` comments, so the output of several tags can be
+//! concatenated into one scrape response; Prometheus treats the metrics as
+//! untyped.
+
+use std::fmt::Write;
+
+use crate::ruuvi_data::RuuviData;
+
+/// Prometheus metric name of a field of `readings()`, `None` for fields
+/// that are not measurements
+fn metric_name(field: &str) -> Option<&'static str> {
+    Some(match field {
+        "temperature" => "ruuvi_temperature_celsius",
+        "humidity" => "ruuvi_humidity_percent",
+        "pressure" => "ruuvi_pressure_hectopascals",
+        "acceleration_x" => "ruuvi_acceleration_x_milli_g",
+        "acceleration_y" => "ruuvi_acceleration_y_milli_g",
+        "acceleration_z" => "ruuvi_acceleration_z_milli_g",
+        "battery_voltage" => "ruuvi_battery_voltage_millivolts",
+        "tx_power" => "ruuvi_tx_power_dbm",
+        "movement_counter" => "ruuvi_movement_counter",
+        "measurement_sequence" => "ruuvi_measurement_sequence",
+        "pm1_0" => "ruuvi_pm1_0_micrograms_per_cubic_meter",
+        "pm2_5" => "ruuvi_pm2_5_micrograms_per_cubic_meter",
+        "pm4_0" => "ruuvi_pm4_0_micrograms_per_cubic_meter",
+        "pm10_0" => "ruuvi_pm10_0_micrograms_per_cubic_meter",
+        "co2" => "ruuvi_co2_ppm",
+        "voc_index" => "ruuvi_voc_index",
+        "nox_index" => "ruuvi_nox_index",
+        "luminosity" => "ruuvi_luminosity_lux",
+        _ => return None,
+    })
+}
+
+/// Escape a label value: backslash, double quote and line feed
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+pub(crate) fn to_prometheus(data: &RuuviData, mac_label: &str) -> String {
+    let mac_label = escape_label(mac_label);
+    data.readings()
+        .filter_map(|(field, value)| Some((metric_name(field)?, value)))
+        .fold(String::new(), |mut output, (name, value)| {
+            let _ = writeln!(output, "{name}{{mac=\"{mac_label}\"}} {value}");
+            output
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v5_valid() {
+        let data = crate::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+            .unwrap()
+            .rounded();
+        let output = data.to_prometheus("CB:B8:33:4C:88:4F");
+
+        for line in [
+            r#"ruuvi_temperature_celsius{mac="CB:B8:33:4C:88:4F"} 24.3"#,
+            r#"ruuvi_humidity_percent{mac="CB:B8:33:4C:88:4F"} 53.49"#,
+            r#"ruuvi_pressure_hectopascals{mac="CB:B8:33:4C:88:4F"} 1000.44"#,
+            r#"ruuvi_acceleration_z_milli_g{mac="CB:B8:33:4C:88:4F"} 1036"#,
+            r#"ruuvi_battery_voltage_millivolts{mac="CB:B8:33:4C:88:4F"} 2977"#,
+            r#"ruuvi_tx_power_dbm{mac="CB:B8:33:4C:88:4F"} 4"#,
+            r#"ruuvi_movement_counter{mac="CB:B8:33:4C:88:4F"} 66"#,
+            r#"ruuvi_measurement_sequence{mac="CB:B8:33:4C:88:4F"} 205"#,
+        ] {
+            assert!(output.lines().any(|l| l == line), "{line} not in\n{output}");
+        }
+        assert_eq!(output.lines().count(), 10);
+        assert!(!output.contains("mac_address"));
+    }
+
+    #[test]
+    fn test_absent_fields_omitted() {
+        let data = crate::decode("0201061BFF9904058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF")
+            .unwrap();
+        assert_eq!(data.to_prometheus("tag"), "");
+
+        let data = crate::decode("17FF990406170C5668C79EFFFFFFFFFFFFD9FFCDC04C884F").unwrap();
+        let output = data.to_prometheus("tag");
+        assert!(!output.contains("NaN"));
+        assert!(!output.contains("ruuvi_co2_ppm"));
+        assert!(output.contains("ruuvi_luminosity_lux{mac=\"tag\"}"));
+        assert!(!output.contains("flags"));
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("a\nb"), r"a\nb");
+    }
+}
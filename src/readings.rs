@@ -0,0 +1,78 @@
+//! Field-by-field view of decoded readings
+//!
+//! `readings()` on [`crate::v5::DataFormatV5`], [`crate::v6::DataFormatV6`],
+//! [`crate::e1::DataFormatE1`] and [`crate::RuuviData`] yields each available
+//! field as a `(name, value)` pair, for generic display, CSV export or tag
+//! generation without listing every field by hand.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Value of one decoded field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ReadingValue {
+    F64(f64),
+    I8(i8),
+    I16(i16),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    String(String),
+}
+
+impl fmt::Display for ReadingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::F64(value) => value.fmt(f),
+            Self::I8(value) => value.fmt(f),
+            Self::I16(value) => value.fmt(f),
+            Self::U8(value) => value.fmt(f),
+            Self::U16(value) => value.fmt(f),
+            Self::U32(value) => value.fmt(f),
+            Self::String(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Drop the unavailable fields of a `(name, value)` list
+pub(crate) fn available<const N: usize>(
+    fields: [(&'static str, Option<ReadingValue>); N],
+) -> impl Iterator<Item = (&'static str, ReadingValue)> {
+    fields
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(ReadingValue::F64(24.3), "24.3")]
+    #[case(ReadingValue::I8(-4), "-4")]
+    #[case(ReadingValue::U32(16_777_214), "16777214")]
+    #[case(ReadingValue::String("cbb8334c884f".into()), "cbb8334c884f")]
+    fn test_display(#[case] value: ReadingValue, #[case] expected: &str) {
+        assert_eq!(value.to_string(), expected);
+    }
+
+    #[test]
+    fn test_available_skips_none() {
+        let fields = available([
+            ("temperature", Some(ReadingValue::F64(24.3))),
+            ("humidity", None),
+            ("flags", Some(ReadingValue::U8(0))),
+        ]);
+        assert_eq!(
+            fields.collect::<Vec<_>>(),
+            [
+                ("temperature", ReadingValue::F64(24.3)),
+                ("flags", ReadingValue::U8(0)),
+            ]
+        );
+    }
+}
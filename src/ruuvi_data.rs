@@ -1,15 +1,23 @@
 //! Data types for Ruuvi sensor data
 //!
-//! These types match the TypeScript interfaces in the shared package
+//! These types match the TypeScript interfaces in the shared package. Enable the
+//! `camel-case` feature to serialize sensor fields in camelCase (`pm25`,
+//! `measurementSequence`, `macAddress`, ...) as the TypeScript side expects.
 
-use crate::air_quality::calc_aqi;
+use crate::air_quality::{AirQualityReading, calc_aqi};
 use crate::error::{DecodeError, Result};
+use crate::mac;
+use crate::prometheus;
+use crate::readings::ReadingValue;
+use crate::units::Luminosity;
 use crate::{
     e1::{self, DataFormatE1},
     v5::{self, DataFormatV5},
     v6::{self, DataFormatV6},
 };
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuuviGatewayEvent {
@@ -22,18 +30,159 @@ pub struct RuuviGatewayEvent {
     pub coords: Option<String>,
 }
 
+impl RuuviGatewayEvent {
+    /// Decode the BLE advertisement carried in `data`
+    ///
+    /// The event itself is left untouched, so gateway metadata such as `rssi`,
+    /// `gw_mac` and the timestamps stay available to the caller.
+    ///
+    /// # Errors
+    ///
+    /// * `DecodeError::MissingManufacturerId` - No Ruuvi manufacturer data in `data`
+    /// * Any error returned by the format specific decoder
+    pub fn decoded(&self) -> Result<RuuviData> {
+        crate::decode_all(&self.data)
+            .into_iter()
+            .next()
+            .unwrap_or(Err(DecodeError::MissingManufacturerId))
+    }
+
+    /// Decode `data`, keeping the reception context of the event
+    ///
+    /// The timestamp is `ts`, or `gwts` when `ts` is missing, in seconds since
+    /// the epoch; see [`RuuviGatewayEvent::timestamp`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RuuviGatewayEvent::decoded`]
+    pub fn into_reading(self) -> Result<ReadingWithContext> {
+        Ok(ReadingWithContext {
+            data: self.decoded()?,
+            rssi: self.rssi,
+            timestamp: self.ts.or(self.gwts),
+            gw_mac: self.gw_mac,
+        })
+    }
+
+    /// Parse the gateway `coords` (`"lat,lon"`) into latitude and longitude
+    ///
+    /// Returns `None` when coordinates are missing, empty or malformed, or
+    /// not a position on Earth: latitude outside ±90°, longitude outside
+    /// ±180°, NaN or infinite.
+    #[must_use]
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let (lat, lon) = self.coords.as_deref()?.split_once(',')?;
+        let lat = lat.trim().parse().ok()?;
+        let lon = lon.trim().parse().ok()?;
+        ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)).then_some((lat, lon))
+    }
+
+    /// Time the advertisement was received, from `ts` or else `gwts`
+    ///
+    /// `ts` is when the gateway received the advertisement and `gwts` when it
+    /// sent the message, so `ts` is preferred. Both are seconds since the epoch.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        let seconds = self.ts.or(self.gwts)?;
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+    }
+
+    /// Time elapsed since [`RuuviGatewayEvent::timestamp`]
+    ///
+    /// Returns `None` without a timestamp, or when the timestamp is in the
+    /// future, e.g. because of clock skew between the gateway and this host.
+    /// Not available on `wasm32`, which has no system clock.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        SystemTime::now().duration_since(self.timestamp()?).ok()
+    }
+
+    /// Parse a Ruuvi Gateway event from its JSON representation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON does not match the gateway event shape
+    #[cfg(feature = "json")]
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Decoded reading together with how the gateway received it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadingWithContext {
+    /// Decoded sensor values
+    pub data: RuuviData,
+    /// Signal strength of the advertisement in dBm
+    pub rssi: i32,
+    /// MAC address of the receiving gateway
+    pub gw_mac: String,
+    /// Reception time in seconds since the epoch
+    pub timestamp: Option<u64>,
+}
+
+/// Parse a Ruuvi Gateway `/history` response mapping sensor MAC to its latest event
+///
+/// Events are returned ordered by MAC address.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not an object of gateway events
+#[cfg(feature = "json")]
+pub fn parse_gateway_history(json: &str) -> serde_json::Result<Vec<(String, RuuviGatewayEvent)>> {
+    let history: std::collections::BTreeMap<String, RuuviGatewayEvent> =
+        serde_json::from_str(json)?;
+    Ok(history.into_iter().collect())
+}
+
+/// Parse a Ruuvi Gateway `/history` response and decode every event
+///
+/// See [`parse_gateway_history`]; each event is decoded with
+/// [`RuuviGatewayEvent::decoded`], so one bad advertisement does not fail the batch.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not an object of gateway events
+#[cfg(feature = "json")]
+pub fn decode_gateway_history(json: &str) -> serde_json::Result<Vec<(String, Result<RuuviData>)>> {
+    Ok(parse_gateway_history(json)?
+        .into_iter()
+        .map(|(mac, event)| {
+            let decoded = event.decoded();
+            (mac, decoded)
+        })
+        .collect())
+}
+
 /// Supported Ruuvi data formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DataFormat {
     /// Data Format 5 (`RAWv2`)
     V5 = 5,
     /// Data Format 6 (`RAWv3`)
     V6 = 6,
-    /// Data Format E1 (Encrypted)
+    /// Data Format E1 (Extended v1)
     E1 = 0xE1,
 }
 
 impl DataFormat {
+    /// All supported data formats, in format byte order
+    #[must_use]
+    pub fn all() -> [Self; 3] {
+        [Self::V5, Self::V6, Self::E1]
+    }
+
+    /// Human readable format name as used in the Ruuvi documentation
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::V5 => "RAWv2",
+            Self::V6 => "RAWv3",
+            Self::E1 => "E1",
+        }
+    }
+
     /// Create `DataFormat` from u8 value
     #[must_use]
     pub fn from_u8(value: u8) -> Option<Self> {
@@ -45,6 +194,48 @@ impl DataFormat {
         }
     }
 
+    /// Detect the data format from the first byte of a payload, without decoding it
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        bytes.first().copied().and_then(Self::from_u8)
+    }
+
+    /// Detect a shortened "heartbeat" frame: a known format byte followed by
+    /// fewer bytes than the format's sensor payload
+    ///
+    /// Lets callers tell a short Ruuvi frame apart from data that is not Ruuvi
+    /// at all, for which [`DataFormat::detect`] returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::DataFormat;
+    ///
+    /// assert_eq!(DataFormat::detect_heartbeat(&[0x05, 0x12, 0xFC]), Some(DataFormat::V5));
+    /// assert_eq!(DataFormat::detect_heartbeat(&[0x63, 0x12, 0xFC]), None);
+    /// ```
+    #[must_use]
+    pub fn detect_heartbeat(bytes: &[u8]) -> Option<Self> {
+        Self::detect(bytes).filter(|format| bytes.len() < format.payload_length())
+    }
+
+    /// Check that a payload (format byte included) has a valid length for this format
+    ///
+    /// # Errors
+    ///
+    /// * `DecodeError::InvalidLength` - Length does not match this format
+    pub fn validate_length(&self, bytes: &[u8]) -> Result<()> {
+        let expected = self.payload_with_mac_length();
+        let valid = bytes.len() == expected
+            || (*self == Self::E1 && bytes.len() == e1::PAYLOAD_WITH_MAC_AND_FLAGS_LENGTH);
+
+        if valid {
+            Ok(())
+        } else {
+            Err(DecodeError::invalid_length(expected, bytes.len()))
+        }
+    }
+
     /// Get the expected payload length in bytes for this format
     #[must_use]
     pub fn payload_length(&self) -> usize {
@@ -66,6 +257,24 @@ impl DataFormat {
     }
 }
 
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Ruuvi hardware a reading most likely came from, see [`RuuviData::sensor_model`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensorModel {
+    /// Classic `RuuviTag` environmental sensor
+    RuuviTag,
+    /// Ruuvi Air quality monitor
+    RuuviAir,
+    /// Air quality format without any air quality reading, e.g. a third party
+    /// device or a Ruuvi Air whose sensors have not started yet
+    Unknown,
+}
+
 /// Unified enum for all supported Ruuvi data formats
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "format")]
@@ -75,7 +284,7 @@ pub enum RuuviData {
     /// Data Format 6 (`RAWv3`)
     #[allow(dead_code)]
     V6(DataFormatV6),
-    /// Data Format E1 (Encrypted)
+    /// Data Format E1 (Extended v1)
     #[allow(dead_code)]
     E1(DataFormatE1),
 }
@@ -85,9 +294,25 @@ impl RuuviData {
     ///
     /// # Errors
     ///
-    /// Returns an error if the data is not a valid Ruuvi data format
+    /// Returns an error if the data is not a valid Ruuvi data format, including
+    /// `DecodeError::InvalidLength` for an empty slice or a heartbeat frame,
+    /// see [`DataFormat::detect_heartbeat`]
     pub fn decode(data: &[u8]) -> Result<Self> {
-        match data[0] {
+        let Some(&format) = data.first() else {
+            return Err(DecodeError::InvalidLength(
+                "Payload is empty, format byte is missing".into(),
+            ));
+        };
+
+        if let Some(format) = DataFormat::detect_heartbeat(data) {
+            return Err(DecodeError::InvalidLength(format!(
+                "{format} heartbeat frame of {} bytes carries no measurements, expected {} bytes",
+                data.len(),
+                format.payload_with_mac_length()
+            )));
+        }
+
+        match format {
             5 => Ok(Self::V5(v5::decode(data)?)),
             6 => Ok(Self::V6(v6::decode(data)?)),
             0xE1 => Ok(Self::E1(e1::decode(data)?)),
@@ -95,6 +320,163 @@ impl RuuviData {
         }
     }
 
+    /// Data format this reading was decoded from
+    #[must_use]
+    pub fn format(&self) -> DataFormat {
+        match self {
+            Self::V5(_) => DataFormat::V5,
+            Self::V6(_) => DataFormat::V6,
+            Self::E1(_) => DataFormat::E1,
+        }
+    }
+
+    /// Present measurements as Prometheus text format sample lines
+    ///
+    /// One `ruuvi_<field>_<unit>{mac="..."} <value>` line per available
+    /// measurement, e.g. `ruuvi_temperature_celsius{mac="cbb8334c884f"} 24.3`.
+    /// Missing fields are omitted rather than written as `NaN`; the MAC
+    /// address, flags and reserved bytes are not measurements and are skipped.
+    /// Values are written as decoded, see [`Self::rounded`] to drop float noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `mac_label` - Value of the `mac` label, escaped as needed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let data = ruuvi_decoders::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+    ///     .unwrap()
+    ///     .rounded();
+    /// let metrics = data.to_prometheus("cbb8334c884f");
+    /// assert!(metrics.contains("ruuvi_temperature_celsius{mac=\"cbb8334c884f\"} 24.3\n"));
+    /// ```
+    #[must_use]
+    pub fn to_prometheus(&self, mac_label: &str) -> String {
+        prometheus::to_prometheus(self, mac_label)
+    }
+
+    /// Replace the MAC address with a deterministic pseudonym, for sharing captures
+    ///
+    /// Sensor fields are left intact. The pseudonym is the leading bytes of a
+    /// hash of the address, so readings of one tag stay grouped; it is not a
+    /// cryptographic hash. Placeholders such as `invalid` are kept. See
+    /// [`crate::mac::anonymize_mac_in_hex`] to scrub payloads before decoding.
+    pub fn anonymize_mac(&mut self) {
+        let mac_address = match self {
+            Self::V5(data) => &mut data.mac_address,
+            Self::V6(data) => &mut data.mac_address,
+            Self::E1(data) => &mut data.mac_address,
+        };
+        mac::anonymize_mac_address(mac_address);
+    }
+
+    /// Hardware that most likely sent this reading
+    ///
+    /// The formats do not carry a model identifier, so it is inferred:
+    ///
+    /// * Data Format 5 is broadcast by the `RuuviTag` family
+    /// * Formats 6 and E1 are broadcast by Ruuvi Air, but only count as such
+    ///   when at least one particulate matter, CO2, VOC or `NOx` reading is
+    ///   present; otherwise the model is [`SensorModel::Unknown`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::{decode, SensorModel};
+    ///
+    /// let data = decode("17FF990406170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+    /// assert_eq!(data.sensor_model(), SensorModel::RuuviAir);
+    /// ```
+    #[must_use]
+    pub fn sensor_model(&self) -> SensorModel {
+        let Ok(air) = AirQualityReading::try_from(self) else {
+            return SensorModel::RuuviTag;
+        };
+
+        let has_air_quality = [air.pm1_0, air.pm2_5, air.pm4_0, air.pm10_0]
+            .iter()
+            .any(Option::is_some)
+            || [air.co2, air.voc_index, air.nox_index]
+                .iter()
+                .any(Option::is_some);
+        if has_air_quality {
+            SensorModel::RuuviAir
+        } else {
+            SensorModel::Unknown
+        }
+    }
+
+    /// Whether `other` is the same format and reports the same physical conditions
+    ///
+    /// See `same_measurement()` on the format specific structs.
+    #[must_use]
+    pub fn same_measurement(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::V5(a), Self::V5(b)) => a.same_measurement(b),
+            (Self::V6(a), Self::V6(b)) => a.same_measurement(b),
+            (Self::E1(a), Self::E1(b)) => a.same_measurement(b),
+            _ => false,
+        }
+    }
+
+    /// Available fields as `(name, value)` pairs, skipping `None` fields
+    ///
+    /// See `readings()` on the format specific structs.
+    #[must_use]
+    pub fn readings(&self) -> Box<dyn Iterator<Item = (&'static str, ReadingValue)> + '_> {
+        match self {
+            Self::V5(data) => Box::new(data.readings()),
+            Self::V6(data) => Box::new(data.readings()),
+            Self::E1(data) => Box::new(data.readings()),
+        }
+    }
+
+    /// Copy with float fields snapped to the resolution of the format
+    ///
+    /// See `rounded()` on the format specific structs.
+    #[must_use]
+    pub fn rounded(&self) -> Self {
+        match self {
+            Self::V5(data) => Self::V5(data.rounded()),
+            Self::V6(data) => Self::V6(data.rounded()),
+            Self::E1(data) => Self::E1(data.rounded()),
+        }
+    }
+
+    /// Encode the reading back into its advertisement payload
+    ///
+    /// Produces the format byte, the sensor fields and the MAC address, i.e.
+    /// the bytes [`RuuviData::decode`] accepts, using [`v5::encode`],
+    /// [`v6::encode`] or [`e1::encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field value or the MAC address cannot be
+    /// represented in the data format
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::V5(data) => v5::encode(data),
+            Self::V6(data) => v6::encode(data),
+            Self::E1(data) => e1::encode(data),
+        }
+    }
+
+    /// Serialize the reading to compact JSON
+    ///
+    /// The output is tagged with the `format` discriminator, e.g.
+    /// `{"format":"V5","temperature":24.3,...}`, and can be read back with
+    /// `serde_json::from_str::<RuuviData>`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: every field serializes to a valid JSON value
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("RuuviData serializes to JSON")
+    }
+
     #[must_use]
     pub fn calculate_air_quality(&self) -> Option<f64> {
         match self {
@@ -117,6 +499,17 @@ impl RuuviData {
     }
 }
 
+impl Luminosity for RuuviData {
+    /// `None` for Data Format 5, which has no light sensor
+    fn luminosity_lux(&self) -> Option<f64> {
+        match self {
+            RuuviData::V5(_) => None,
+            RuuviData::V6(v6) => v6.luminosity_lux(),
+            RuuviData::E1(e1) => e1.luminosity_lux(),
+        }
+    }
+}
+
 impl TryFrom<&[u8]> for RuuviData {
     type Error = DecodeError;
 
@@ -128,6 +521,7 @@ impl TryFrom<&[u8]> for RuuviData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn test_decode_valid_data() {
@@ -140,4 +534,444 @@ mod tests {
         )
         .expect("Decoded v5 slice");
     }
+
+    #[test]
+    fn test_decode_empty_slice() {
+        assert!(RuuviData::try_from(&[][..]).is_err());
+        assert!(matches!(
+            RuuviData::decode(&[]),
+            Err(DecodeError::InvalidLength(_))
+        ));
+    }
+
+    #[rstest]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    fn test_encode_round_trip(#[case] hex_str: &str) {
+        let bytes = hex::decode(hex_str).unwrap();
+        let data = RuuviData::decode(&bytes).unwrap();
+        let encoded = data.encode().unwrap();
+        assert_eq!(encoded, bytes);
+        assert_eq!(RuuviData::decode(&encoded).unwrap(), data);
+    }
+
+    #[rstest]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case("E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F")]
+    fn test_same_measurement(#[case] hex_str: &str) {
+        let bytes = hex::decode(hex_str).unwrap();
+        let data = RuuviData::decode(&bytes).unwrap();
+
+        // Bump the measurement sequence, and the MAC address, but keep the sensor values
+        let mut next = bytes.clone();
+        let last = next.len() - 1;
+        next[last] ^= 0xFF;
+        let next = match RuuviData::decode(&next).unwrap() {
+            RuuviData::V5(v5) => RuuviData::V5(DataFormatV5 {
+                measurement_sequence: v5.measurement_sequence.map(|s| s + 1),
+                movement_counter: v5.movement_counter.map(|m| m + 1),
+                ..v5
+            }),
+            RuuviData::V6(v6) => RuuviData::V6(DataFormatV6 {
+                measurement_sequence: v6.measurement_sequence.map(|s| s + 1),
+                ..v6
+            }),
+            RuuviData::E1(e1) => RuuviData::E1(DataFormatE1 {
+                measurement_sequence: e1.measurement_sequence.map(|s| s + 1),
+                ..e1
+            }),
+        };
+        assert_ne!(data, next);
+        assert!(data.same_measurement(&next));
+
+        let warmer = match next {
+            RuuviData::V5(v5) => RuuviData::V5(DataFormatV5 {
+                temperature: Some(25.0),
+                ..v5
+            }),
+            RuuviData::V6(v6) => RuuviData::V6(DataFormatV6 {
+                temperature: Some(25.0),
+                ..v6
+            }),
+            RuuviData::E1(e1) => RuuviData::E1(DataFormatE1 {
+                temperature: Some(25.0),
+                ..e1
+            }),
+        };
+        assert!(!data.same_measurement(&warmer));
+    }
+
+    #[test]
+    fn test_same_measurement_across_formats() {
+        let v5 = crate::decode("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let v6 = crate::decode("990406170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        assert!(!v5.same_measurement(&v6));
+    }
+
+    #[rstest]
+    #[case("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F", 11)]
+    #[case("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF", 1)] // only the MAC address
+    #[case("06170C5668C79E007000C90501D9FFCD004C884F", 12)]
+    #[case(
+        "E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        14
+    )]
+    fn test_readings_count(#[case] hex_str: &str, #[case] expected: usize) {
+        let data = RuuviData::decode(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(data.readings().count(), expected);
+    }
+
+    #[rstest]
+    #[case(&[0x05, 0x12], Some(DataFormat::V5))]
+    #[case(&[0x06], Some(DataFormat::V6))]
+    #[case(&[0xE1, 0x00, 0x00], Some(DataFormat::E1))]
+    #[case(&[0x63], None)]
+    #[case(&[], None)]
+    fn test_detect_format(#[case] bytes: &[u8], #[case] expected: Option<DataFormat>) {
+        assert_eq!(DataFormat::detect(bytes), expected);
+    }
+
+    #[rstest]
+    #[case(&[0x05, 0x12], Some(DataFormat::V5))]
+    #[case(&[0x06], Some(DataFormat::V6))]
+    #[case(&[0xE1; 33], Some(DataFormat::E1))]
+    #[case(&[0xE1; 34], None)] // full sensor payload, MAC missing
+    #[case(&[0x63, 0x12], None)]
+    #[case(&[], None)]
+    fn test_detect_heartbeat(#[case] bytes: &[u8], #[case] expected: Option<DataFormat>) {
+        assert_eq!(DataFormat::detect_heartbeat(bytes), expected);
+    }
+
+    #[test]
+    fn test_decode_heartbeat() {
+        assert_eq!(
+            RuuviData::decode(&[0x05, 0x12, 0xFC]),
+            Err(DecodeError::InvalidLength(
+                "RAWv2 heartbeat frame of 3 bytes carries no measurements, expected 24 bytes"
+                    .into()
+            ))
+        );
+        assert_eq!(
+            RuuviData::decode(&[0x63, 0x12, 0xFC]),
+            Err(DecodeError::UnsupportedFormat(0x63))
+        );
+    }
+
+    #[rstest]
+    #[case(DataFormat::V5, "RAWv2")]
+    #[case(DataFormat::V6, "RAWv3")]
+    #[case(DataFormat::E1, "E1")]
+    fn test_format_name(#[case] format: DataFormat, #[case] name: &str) {
+        assert_eq!(format.name(), name);
+        assert_eq!(format.to_string(), name);
+    }
+
+    #[rstest]
+    #[case::v5(
+        "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        SensorModel::RuuviTag
+    )]
+    #[case::v5_invalid(
+        "0201061BFF9904058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF",
+        SensorModel::RuuviTag
+    )]
+    #[case::v6(
+        "17FF990406170C5668C79E007000C90501D9FFCD004C884F",
+        SensorModel::RuuviAir
+    )]
+    #[case::e1(
+        "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F",
+        SensorModel::RuuviAir
+    )]
+    // Every air quality field at its sentinel
+    #[case::v6_without_air_quality(
+        "17FF990406170C5668C79EFFFFFFFFFFFFD9FFCDC04C884F",
+        SensorModel::Unknown
+    )]
+    fn test_sensor_model(#[case] ble_data: &str, #[case] expected: SensorModel) {
+        assert_eq!(crate::decode(ble_data).unwrap().sensor_model(), expected);
+    }
+
+    #[test]
+    fn test_all_formats() {
+        let all = DataFormat::all();
+        for byte in 0..=u8::MAX {
+            if let Some(format) = DataFormat::from_u8(byte) {
+                assert!(all.contains(&format), "{format} missing from all()");
+            }
+        }
+        assert!(
+            all.iter()
+                .all(|f| DataFormat::from_u8(*f as u8) == Some(*f))
+        );
+    }
+
+    #[rstest]
+    #[case(DataFormat::V5, 24, true)]
+    #[case(DataFormat::V5, 18, false)]
+    #[case(DataFormat::V5, 25, false)]
+    #[case(DataFormat::V6, 20, true)]
+    #[case(DataFormat::V6, 24, false)]
+    #[case(DataFormat::E1, 40, true)]
+    #[case(DataFormat::E1, 44, true)]
+    #[case(DataFormat::E1, 42, false)]
+    fn test_validate_length(#[case] format: DataFormat, #[case] len: usize, #[case] ok: bool) {
+        let bytes = vec![0u8; len];
+        assert_eq!(format.validate_length(&bytes).is_ok(), ok);
+    }
+
+    #[test]
+    fn test_validate_length_error() {
+        assert_eq!(
+            DataFormat::V5.validate_length(&[0x05; 10]),
+            Err(DecodeError::invalid_length(24, 10))
+        );
+    }
+
+    fn gateway_event(data: &str) -> RuuviGatewayEvent {
+        RuuviGatewayEvent {
+            gw_mac: "C8:25:2D:8E:9C:2C".into(),
+            rssi: -62,
+            aoa: vec![],
+            gwts: Some(1_728_719_836),
+            ts: Some(1_728_719_835),
+            data: data.into(),
+            coords: Some(String::new()),
+        }
+    }
+
+    #[test]
+    fn test_gateway_event_decoded() {
+        let event = gateway_event("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F");
+        let data = event.decoded().expect("Decoded gateway event");
+
+        assert!(matches!(data, RuuviData::V5(ref v5) if v5.temperature == Some(24.3)));
+        assert_eq!(event.rssi, -62);
+    }
+
+    #[test]
+    fn test_gateway_event_into_reading() {
+        let json = r#"{
+            "gw_mac": "C8:25:2D:8E:9C:2C",
+            "rssi": -62,
+            "aoa": [],
+            "gwts": 1728719836,
+            "ts": null,
+            "data": "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "coords": ""
+        }"#;
+        let event: RuuviGatewayEvent = serde_json::from_str(json).unwrap();
+        let expected_data = event.decoded().unwrap();
+
+        assert_eq!(
+            event.into_reading(),
+            Ok(ReadingWithContext {
+                data: expected_data,
+                rssi: -62,
+                gw_mac: "C8:25:2D:8E:9C:2C".into(),
+                timestamp: Some(1_728_719_836),
+            })
+        );
+
+        let reading = gateway_event("020106030316910255AA").into_reading();
+        assert_eq!(reading, Err(DecodeError::MissingManufacturerId));
+    }
+
+    #[test]
+    fn test_gateway_event_decoded_without_ruuvi_data() {
+        let event = gateway_event("020106030316910255AA");
+        assert_eq!(event.decoded(), Err(DecodeError::MissingManufacturerId));
+    }
+
+    #[rstest]
+    #[case(Some(1_728_719_835), Some(1_728_719_836), Some(1_728_719_835))]
+    #[case(None, Some(1_728_719_836), Some(1_728_719_836))]
+    #[case(None, None, None)]
+    fn test_gateway_event_timestamp(
+        #[case] ts: Option<u64>,
+        #[case] gwts: Option<u64>,
+        #[case] expected: Option<u64>,
+    ) {
+        let mut event = gateway_event("");
+        event.ts = ts;
+        event.gwts = gwts;
+        assert_eq!(
+            event.timestamp(),
+            expected.map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+        );
+    }
+
+    #[test]
+    fn test_gateway_event_age() {
+        let mut event = gateway_event("");
+        // 2024-10-12, well in the past
+        assert!(event.age().unwrap() > Duration::from_secs(365 * 24 * 3600));
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        event.ts = Some(now.as_secs() + 3600);
+        assert_eq!(event.age(), None);
+
+        event.ts = None;
+        event.gwts = None;
+        assert_eq!(event.age(), None);
+    }
+
+    #[rstest]
+    #[case(Some("60.1699,24.9384"), Some((60.1699, 24.9384)))]
+    #[case(Some(" -33.8688 , 151.2093 "), Some((-33.8688, 151.2093)))]
+    #[case(Some(""), None)]
+    #[case(Some("60.1699"), None)]
+    #[case(Some("60.1699,"), None)]
+    #[case(Some("north,east"), None)]
+    #[case(Some("1,2,3"), None)]
+    #[case(Some("90,180"), Some((90.0, 180.0)))]
+    #[case(Some("-90,-180"), Some((-90.0, -180.0)))]
+    #[case(Some("90.1,24.9"), None)]
+    #[case(Some("-90.1,24.9"), None)]
+    #[case(Some("60.1,180.1"), None)]
+    #[case(Some("60.1,-180.1"), None)]
+    #[case(Some("NaN,24.9"), None)]
+    #[case(Some("60.1,NaN"), None)]
+    #[case(Some("inf,24.9"), None)]
+    #[case(Some("60.1,-inf"), None)]
+    #[case(None, None)]
+    fn test_gateway_event_coordinates(
+        #[case] coords: Option<&str>,
+        #[case] expected: Option<(f64, f64)>,
+    ) {
+        let mut event = gateway_event("");
+        event.coords = coords.map(String::from);
+        assert_eq!(event.coordinates(), expected);
+    }
+
+    #[rstest]
+    #[case::v5("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::v5_invalid("1BFF99040580008000800080008000FFFFFFFFFFFFFFFFFFFFFFFFFF")]
+    #[case::v6("17FF990406170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case::v6_maximum("17FF9904067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F")]
+    #[case::e1(
+        "2BFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+    )]
+    #[case::e1_minimum(
+        "2BFF9904E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F"
+    )]
+    fn test_serde_round_trip(#[case] ble_data: &str) {
+        let data = crate::decode(ble_data).unwrap();
+        let json = serde_json::to_string(&data).unwrap();
+        let restored: RuuviData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, data, "{json}");
+        assert_eq!(restored.format(), data.format());
+    }
+
+    #[cfg(feature = "camel-case")]
+    #[test]
+    fn test_camel_case_field_names() {
+        let ble_data = "2BFFFF9904E1170C5668C79E0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F";
+        let json = serde_json::to_value(crate::decode(ble_data).unwrap()).unwrap();
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+
+        for key in [
+            "format",
+            "pm1",
+            "pm25",
+            "pm4",
+            "pm10",
+            "vocIndex",
+            "noxIndex",
+            "measurementSequence",
+            "macAddress",
+        ] {
+            assert!(keys.contains(&key), "missing {key} in {keys:?}");
+        }
+        assert!(!keys.iter().any(|key| key.contains('_')), "{keys:?}");
+    }
+
+    #[cfg(feature = "mac-colons")]
+    #[rstest]
+    #[case(
+        "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        "CB:B8:33:4C:88:4F"
+    )]
+    #[case("17FF990406170C5668C79E007000C90501D9FFCD004C884F", "4C:88:4F")]
+    fn test_mac_colons(#[case] ble_data: &str, #[case] expected: &str) {
+        let data = crate::decode(ble_data).unwrap();
+        let json = serde_json::to_value(&data).unwrap();
+        let key = if cfg!(feature = "camel-case") {
+            "macAddress"
+        } else {
+            "mac_address"
+        };
+
+        assert_eq!(json[key], expected);
+        assert_eq!(serde_json::from_value::<RuuviData>(json).unwrap(), data);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_json() {
+        let data = crate::decode("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let json = data.to_json();
+
+        let expected = match (cfg!(feature = "camel-case"), cfg!(feature = "mac-colons")) {
+            (false, false) => {
+                r#"{"format":"V5","mac_address":"cbb8334c884f","temperature":24.3,"humidity":53.49,"pressure":1000.44,"acceleration_x":4,"acceleration_y":-4,"acceleration_z":1036,"battery_voltage":2977,"tx_power":4,"movement_counter":66,"measurement_sequence":205}"#
+            }
+            (false, true) => {
+                r#"{"format":"V5","mac_address":"CB:B8:33:4C:88:4F","temperature":24.3,"humidity":53.49,"pressure":1000.44,"acceleration_x":4,"acceleration_y":-4,"acceleration_z":1036,"battery_voltage":2977,"tx_power":4,"movement_counter":66,"measurement_sequence":205}"#
+            }
+            (true, false) => {
+                r#"{"format":"V5","macAddress":"cbb8334c884f","temperature":24.3,"humidity":53.49,"pressure":1000.44,"accelerationX":4,"accelerationY":-4,"accelerationZ":1036,"batteryVoltage":2977,"txPower":4,"movementCounter":66,"measurementSequence":205}"#
+            }
+            (true, true) => {
+                r#"{"format":"V5","macAddress":"CB:B8:33:4C:88:4F","temperature":24.3,"humidity":53.49,"pressure":1000.44,"accelerationX":4,"accelerationY":-4,"accelerationZ":1036,"batteryVoltage":2977,"txPower":4,"movementCounter":66,"measurementSequence":205}"#
+            }
+        };
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<RuuviData>(&json).unwrap(), data);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_gateway_event_from_json() {
+        let json = r#"{
+            "gw_mac": "C8:25:2D:8E:9C:2C",
+            "rssi": -62,
+            "aoa": [],
+            "gwts": 1728719836,
+            "ts": 1728719835,
+            "data": "0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+            "coords": ""
+        }"#;
+        let event = RuuviGatewayEvent::from_json(json).expect("Parsed gateway event");
+
+        assert_eq!(event.gw_mac, "C8:25:2D:8E:9C:2C");
+        assert_eq!(event.ts, Some(1_728_719_835));
+        assert!(matches!(event.decoded(), Ok(RuuviData::V5(_))));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_gateway_history() {
+        let json = include_str!("../tests/fixtures/gateway_history.json");
+
+        let events = parse_gateway_history(json).expect("Parsed history");
+        let macs: Vec<&str> = events.iter().map(|(mac, _)| mac.as_str()).collect();
+        assert_eq!(macs, ["CB:B8:33:4C:88:4F", "D1:6F:0A:4C:88:4F"]);
+        assert_eq!(events[1].1.rssi, -71);
+
+        let decoded = decode_gateway_history(json).expect("Parsed history");
+        assert!(matches!(decoded[0].1, Ok(RuuviData::V5(_))));
+        assert!(matches!(decoded[1].1, Ok(RuuviData::V6(_))));
+
+        assert!(parse_gateway_history("[]").is_err());
+        assert_eq!(parse_gateway_history("{}").unwrap().len(), 0);
+    }
 }
@@ -0,0 +1,180 @@
+//! Wrapping counter arithmetic for measurement sequence numbers
+//!
+//! Sequence numbers count up and wrap back to 0 after their highest valid
+//! value: 65534 for V5, 255 for V6 and 16777214 for E1. The V5 movement
+//! counter wraps the same way after 254.
+
+/// Number of distinct V5 sequence numbers (0..=65534, 65535 marks invalid)
+pub const V5_SEQUENCE_MODULUS: u32 = 65_535;
+/// Number of distinct V6 sequence numbers (0..=255)
+pub const V6_SEQUENCE_MODULUS: u32 = 256;
+/// Number of distinct E1 sequence numbers (0..=16777214, 0xFFFFFF marks invalid)
+pub const E1_SEQUENCE_MODULUS: u32 = 0x00FF_FFFF;
+
+/// Check whether `current` was sent after `other` on a counter wrapping at `modulus`
+///
+/// Uses serial number arithmetic: `current` is newer when it is ahead of
+/// `other` by less than half the counter range, so a counter that just
+/// wrapped around is still recognised as newer. Equal values are not newer.
+/// Inputs at or above `modulus` are reduced modulo `modulus` first, and a
+/// `modulus` of 0 makes nothing newer.
+#[must_use]
+pub fn is_newer(current: u32, other: u32, modulus: u32) -> bool {
+    if modulus == 0 {
+        return false;
+    }
+    let modulus = u64::from(modulus);
+    let ahead = (u64::from(current) % modulus + modulus - u64::from(other) % modulus) % modulus;
+    ahead != 0 && ahead < modulus.div_ceil(2)
+}
+
+/// Number of distinct V5 movement counter values (0..=254, 255 marks invalid)
+pub const MOVEMENT_COUNTER_MODULUS: u8 = 255;
+
+/// Count movements detected between two successive movement counter readings
+///
+/// Handles the counter wrapping from 254 back to 0. The value 255 means
+/// "not available", so if either reading is 255 the result is 0 (no change).
+///
+/// # Arguments
+///
+/// * `prev` - Earlier movement counter reading
+/// * `cur` - Later movement counter reading
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::sequence::movement_delta;
+///
+/// assert_eq!(movement_delta(10, 13), 3);
+/// assert_eq!(movement_delta(253, 1), 3);
+/// assert_eq!(movement_delta(10, 255), 0);
+/// ```
+#[must_use]
+pub fn movement_delta(prev: u8, cur: u8) -> u8 {
+    if prev == MOVEMENT_COUNTER_MODULUS || cur == MOVEMENT_COUNTER_MODULUS {
+        return 0;
+    }
+    let ahead = u16::from(cur) + u16::from(MOVEMENT_COUNTER_MODULUS) - u16::from(prev);
+    u8::try_from(ahead % u16::from(MOVEMENT_COUNTER_MODULUS)).unwrap_or(0)
+}
+
+/// Count the V5 measurements lost between two successive sequence numbers
+///
+/// Handles the counter wrapping from 65534 back to 0. Repeated readings of
+/// the same measurement count as nothing missed, and so does 65535 ("not
+/// available") in either reading.
+///
+/// # Arguments
+///
+/// * `prev` - Sequence number of the earlier reading
+/// * `cur` - Sequence number of the later reading
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::sequence::missed_since;
+///
+/// assert_eq!(missed_since(10, 11), 0);
+/// assert_eq!(missed_since(10, 14), 3);
+/// assert_eq!(missed_since(65_533, 1), 2);
+/// ```
+#[must_use]
+pub fn missed_since(prev: u16, cur: u16) -> u16 {
+    if prev == u16::MAX || cur == u16::MAX {
+        return 0;
+    }
+    let ahead = (u32::from(cur) + V5_SEQUENCE_MODULUS - u32::from(prev)) % V5_SEQUENCE_MODULUS;
+    u16::try_from(ahead.saturating_sub(1)).unwrap_or(0)
+}
+
+/// Count the V6 measurements lost between two successive sequence numbers
+///
+/// Like [`missed_since`], for the V6 counter that wraps from 255 back to 0
+/// and has no "not available" value.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::sequence::missed_since_v6;
+///
+/// assert_eq!(missed_since_v6(254, 1), 2);
+/// ```
+#[must_use]
+pub fn missed_since_v6(prev: u8, cur: u8) -> u8 {
+    cur.wrapping_sub(prev).saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 0, V5_SEQUENCE_MODULUS, true)]
+    #[case(0, 1, V5_SEQUENCE_MODULUS, false)]
+    #[case(5, 5, V5_SEQUENCE_MODULUS, false)]
+    #[case(0, 65_534, V5_SEQUENCE_MODULUS, true)] // wrapped
+    #[case(65_534, 0, V5_SEQUENCE_MODULUS, false)]
+    #[case(10, 65_530, V5_SEQUENCE_MODULUS, true)]
+    #[case(0, 255, V6_SEQUENCE_MODULUS, true)] // wrapped
+    #[case(255, 0, V6_SEQUENCE_MODULUS, false)]
+    #[case(200, 100, V6_SEQUENCE_MODULUS, true)]
+    #[case(100, 200, V6_SEQUENCE_MODULUS, false)]
+    #[case(0, 16_777_214, E1_SEQUENCE_MODULUS, true)] // wrapped
+    #[case(16_777_214, 0, E1_SEQUENCE_MODULUS, false)]
+    #[case(0, 70_000, 65_536, false)] // other reduces to 4464
+    #[case(70_000, 0, 65_536, true)]
+    #[case(65_536, 65_535, 65_536, true)] // current reduces to 0, wrapped
+    #[case(256, 0, V6_SEQUENCE_MODULUS, false)] // same value after reduction
+    #[case(1, u32::MAX, u32::MAX, true)]
+    #[case(u32::MAX - 1, 1, u32::MAX, false)]
+    #[case(u32::MAX, 0, u32::MAX, false)] // u32::MAX reduces to 0
+    #[case(1, 0, 0, false)] // zero modulus
+    fn newer(
+        #[case] current: u32,
+        #[case] other: u32,
+        #[case] modulus: u32,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_newer(current, other, modulus), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0, 0)]
+    #[case(10, 13, 3)]
+    #[case(254, 0, 1)] // wrapped
+    #[case(253, 1, 3)] // wrapped
+    #[case(0, 254, 254)]
+    #[case(255, 10, 0)] // not available
+    #[case(10, 255, 0)]
+    #[case(255, 255, 0)]
+    fn movement(#[case] prev: u8, #[case] cur: u8, #[case] expected: u8) {
+        assert_eq!(movement_delta(prev, cur), expected);
+    }
+
+    #[rstest]
+    #[case(10, 11, 0)]
+    #[case(10, 14, 3)]
+    #[case(10, 10, 0)] // same measurement received twice
+    #[case(65_533, 1, 2)]
+    #[case(65_534, 0, 0)]
+    #[case(65_534, 65_533, 65_533)] // one short of a full lap
+    #[case(0, 65_534, 65_533)]
+    #[case(65_535, 3, 0)]
+    #[case(3, 65_535, 0)]
+    fn missed_v5(#[case] prev: u16, #[case] cur: u16, #[case] expected: u16) {
+        assert_eq!(missed_since(prev, cur), expected);
+    }
+
+    #[rstest]
+    #[case(10, 11, 0)]
+    #[case(10, 14, 3)]
+    #[case(10, 10, 0)]
+    #[case(254, 1, 2)]
+    #[case(255, 0, 0)]
+    #[case(0, 255, 254)]
+    fn missed_v6(#[case] prev: u8, #[case] cur: u8, #[case] expected: u8) {
+        assert_eq!(missed_since_v6(prev, cur), expected);
+    }
+}
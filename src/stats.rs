@@ -0,0 +1,116 @@
+//! Rolling decode statistics for long-running scanners
+
+use crate::{Result, RuuviData};
+use std::collections::BTreeMap;
+
+/// Tally of decode outcomes, with failures counted per `DecodeError` variant
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::{decode, stats::DecodeStats};
+///
+/// let mut stats = DecodeStats::default();
+/// stats.record(&decode("99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F"));
+/// stats.record(&decode("not hex"));
+///
+/// assert_eq!(stats.total(), 2);
+/// assert_eq!(stats.error_count("InvalidHex"), 1);
+/// assert_eq!(stats.success_rate(), Some(0.5));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeStats {
+    successes: u64,
+    errors: BTreeMap<&'static str, u64>,
+}
+
+impl DecodeStats {
+    /// Record the outcome of a single decode
+    pub fn record(&mut self, result: &Result<RuuviData>) {
+        match result {
+            Ok(_) => self.successes += 1,
+            Err(err) => *self.errors.entry(err.kind()).or_default() += 1,
+        }
+    }
+
+    /// Number of successful decodes
+    #[must_use]
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+
+    /// Number of failed decodes, of any kind
+    #[must_use]
+    pub fn failures(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// Number of recorded decodes
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.successes + self.failures()
+    }
+
+    /// Number of failures for an error variant, by its `DecodeError::kind` name
+    #[must_use]
+    pub fn error_count(&self, kind: &str) -> u64 {
+        self.errors.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Failure counts per error variant, ordered by variant name
+    pub fn errors(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        self.errors.iter().map(|(kind, count)| (*kind, *count))
+    }
+
+    /// Fraction of recorded decodes that succeeded, `None` before anything was recorded
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn success_rate(&self) -> Option<f64> {
+        let total = self.total();
+        (total > 0).then(|| self.successes as f64 / total as f64)
+    }
+
+    /// Clear all counters
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DecodeError, decode};
+
+    #[test]
+    fn test_record_mixed_results() {
+        let mut stats = DecodeStats::default();
+        assert_eq!(stats.success_rate(), None);
+
+        stats.record(&decode(
+            "99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        ));
+        stats.record(&decode(
+            "99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F",
+        ));
+        stats.record(&decode("ZZ"));
+        stats.record(&decode("9904630000"));
+        stats.record(&Err(DecodeError::UnsupportedFormat(0x02)));
+        stats.record(&Err(DecodeError::NoData));
+
+        assert_eq!(stats.successes(), 2);
+        assert_eq!(stats.failures(), 4);
+        assert_eq!(stats.total(), 6);
+        assert_eq!(stats.error_count("InvalidHex"), 1);
+        assert_eq!(stats.error_count("UnsupportedFormat"), 2);
+        assert_eq!(stats.error_count("NoData"), 1);
+        assert_eq!(stats.error_count("InvalidLength"), 0);
+        assert_eq!(
+            stats.errors().collect::<Vec<_>>(),
+            vec![("InvalidHex", 1), ("NoData", 1), ("UnsupportedFormat", 2)]
+        );
+        assert_eq!(stats.success_rate(), Some(2.0 / 6.0));
+
+        stats.reset();
+        assert_eq!(stats, DecodeStats::default());
+    }
+}
@@ -0,0 +1,310 @@
+//! Unit conversions for decoded sensor values
+//!
+//! All formats report pressure in hPa; these helpers convert it to other
+//! commonly used units, correct humidity for temperature and derive comfort
+//! indexes from temperature and humidity. [`Luminosity`]
+//! reads light levels in lux regardless of how the format encodes them.
+
+/// Pascals in one hectopascal
+pub const PA_PER_HPA: f64 = 100.0;
+/// Pascals in one millimetre of mercury
+pub const PA_PER_MMHG: f64 = 133.322_387_415;
+/// Pascals in one inch of mercury
+pub const PA_PER_INHG: f64 = 3_386.388_640_341;
+
+/// Convert pressure from hPa to Pa
+#[must_use]
+pub fn hpa_to_pa(hpa: f64) -> f64 {
+    hpa * PA_PER_HPA
+}
+
+/// Convert pressure from hPa to mmHg
+#[must_use]
+pub fn hpa_to_mmhg(hpa: f64) -> f64 {
+    hpa_to_pa(hpa) / PA_PER_MMHG
+}
+
+/// Convert pressure from hPa to inHg
+#[must_use]
+pub fn hpa_to_inhg(hpa: f64) -> f64 {
+    hpa_to_pa(hpa) / PA_PER_INHG
+}
+
+/// Altitude scale of the international barometric formula in metres
+const BAROMETRIC_ALTITUDE_SCALE: f64 = 44_330.0;
+/// Exponent of the international barometric formula
+const BAROMETRIC_EXPONENT: f64 = 5.255;
+
+/// Reduce station pressure in hPa to mean sea level pressure in hPa
+///
+/// Uses the international barometric formula for the standard atmosphere.
+/// `altitude_m` is the station altitude in metres and may be negative for
+/// stations below sea level; an altitude of 0 returns the pressure unchanged.
+#[must_use]
+pub fn sea_level_pressure(hpa: f64, altitude_m: f64) -> f64 {
+    if altitude_m == 0.0 {
+        return hpa;
+    }
+    hpa / (1.0 - altitude_m / BAROMETRIC_ALTITUDE_SCALE).powf(BAROMETRIC_EXPONENT)
+}
+
+/// Sensirion `SHT1x` humidity linearization coefficients (12-bit readout)
+const RH_C1: f64 = -2.0468;
+const RH_C2: f64 = 0.0367;
+const RH_C3: f64 = -1.5955e-6;
+/// Sensirion `SHT1x` humidity temperature coefficients
+const RH_T1: f64 = 0.01;
+const RH_T2: f64 = 0.000_08;
+/// Temperature in Celsius at which the sensor needs no compensation
+const RH_REFERENCE_TEMPERATURE: f64 = 25.0;
+
+/// Correct relative humidity from a Sensirion `SHT1x` sensor for temperature
+///
+/// Not for Ruuvi readings: the `SHTC3` and `SHT4x` sensors of Ruuvi devices
+/// already compensate on chip, and correcting their humidity again skews it,
+/// e.g. 50% at 35°C would become 51.3%. Meant for data from other devices with
+/// an uncompensated `SHT1x`.
+///
+/// Applies the `SHT1x` datasheet correction
+/// `RH_true = (T - 25) * (t1 + t2 * SO_RH) + RH_linear` with `t1 = 0.01` and
+/// `t2 = 0.00008`, where the 12-bit sensor output `SO_RH` is recovered from the
+/// linear humidity by inverting `RH_linear = c1 + c2 * SO_RH + c3 * SO_RH²`.
+/// At 25°C the humidity is returned unchanged.
+#[must_use]
+pub fn compensate_humidity_sht1x(temperature: f64, humidity: f64) -> f64 {
+    // Larger root of c3 * SO² + c2 * SO + (c1 - RH) = 0 is outside the 12-bit range
+    let discriminant = RH_C2.mul_add(RH_C2, -4.0 * RH_C3 * (RH_C1 - humidity));
+    let sensor_output = (-RH_C2 + discriminant.max(0.0).sqrt()) / (2.0 * RH_C3);
+
+    (temperature - RH_REFERENCE_TEMPERATURE).mul_add(RH_T2.mul_add(sensor_output, RH_T1), humidity)
+}
+
+/// Lowest temperature in Fahrenheit the heat index is defined for
+const HEAT_INDEX_MIN_FAHRENHEIT: f64 = 80.0;
+/// Rothfusz regression coefficients, for °F and % relative humidity
+const ROTHFUSZ: [f64; 9] = [
+    -42.379,
+    2.049_015_23,
+    10.143_331_27,
+    -0.224_755_41,
+    -0.006_837_83,
+    -0.054_817_17,
+    0.001_228_74,
+    0.000_852_82,
+    -0.000_001_99,
+];
+
+/// US National Weather Service heat index in Celsius
+///
+/// Follows the NWS algorithm: Steadman's simple formula, switching to the
+/// Rothfusz regression with its low and high humidity adjustments once the
+/// result reaches 80°F. `None` below 80°F (26.7°C), where the index is not
+/// defined, or for humidity outside 0..=100%.
+#[must_use]
+pub fn heat_index(temperature: f64, humidity: f64) -> Option<f64> {
+    let (t, rh) = (temperature.mul_add(1.8, 32.0), humidity);
+    if !(HEAT_INDEX_MIN_FAHRENHEIT..).contains(&t) || !(0.0..=100.0).contains(&rh) {
+        return None;
+    }
+
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    let fahrenheit = if simple.midpoint(t) < HEAT_INDEX_MIN_FAHRENHEIT {
+        simple
+    } else {
+        let [c1, c2, c3, c4, c5, c6, c7, c8, c9] = ROTHFUSZ;
+        let regression = c1
+            + c2 * t
+            + c3 * rh
+            + c4 * t * rh
+            + c5 * t * t
+            + c6 * rh * rh
+            + c7 * t * t * rh
+            + c8 * t * rh * rh
+            + c9 * t * t * rh * rh;
+        let adjustment = if rh < 13.0 && (80.0..=112.0).contains(&t) {
+            -(13.0 - rh) / 4.0 * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt()
+        } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+            (rh - 85.0) / 10.0 * (87.0 - t) / 5.0
+        } else {
+            0.0
+        };
+        regression + adjustment
+    };
+
+    Some((fahrenheit - 32.0) / 1.8)
+}
+
+/// Lowest temperature in Celsius Environment Canada reports humidex for
+const HUMIDEX_MIN_TEMPERATURE: f64 = 20.0;
+
+/// Environment Canada humidex, a unitless "feels like" temperature in Celsius
+///
+/// `T + 5/9 * (e - 10)`, where the vapour pressure `e` in hPa follows from the
+/// relative humidity and the Magnus saturation pressure
+/// `6.112 * 10^(7.5 * T / (237.7 + T))`. `None` below 20°C, where humidex is
+/// not reported, or for humidity outside 0..=100%.
+#[must_use]
+pub fn humidex(temperature: f64, humidity: f64) -> Option<f64> {
+    if !(HUMIDEX_MIN_TEMPERATURE..).contains(&temperature) || !(0.0..=100.0).contains(&humidity) {
+        return None;
+    }
+
+    let saturation = 6.112 * 10f64.powf(7.5 * temperature / (237.7 + temperature));
+    let vapour_pressure = saturation * humidity / 100.0;
+    Some(temperature + 5.0 / 9.0 * (vapour_pressure - 10.0))
+}
+
+/// Light level in lux, comparable across data formats
+///
+/// Data Format 6 sends a logarithmic byte code, decoded as
+/// `exp(code * ln(65536) / 254) - 1` and capped at 65535 lx, while E1 sends a
+/// linear 24-bit value of 0.01 lx per bit. Both decode to lux, so readings
+/// from either generation can be compared directly.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::units::Luminosity;
+///
+/// let data = ruuvi_decoders::decode("17FF990406170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+/// assert!(data.luminosity_lux().is_some());
+/// ```
+pub trait Luminosity {
+    /// Luminosity in lux, `None` when unavailable or not measured by the format
+    fn luminosity_lux(&self) -> Option<f64>;
+}
+
+/// Round `value` to the nearest multiple of `1 / steps_per_unit`
+///
+/// Dividing by the whole number of steps, rather than multiplying by the
+/// fractional resolution, yields the float closest to the decimal value, so
+/// `24.300000000000004` snaps to `24.3`.
+pub(crate) fn snap_to_resolution(value: f64, steps_per_unit: f64) -> f64 {
+    (value * steps_per_unit).round() / steps_per_unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EPS: f64 = 1e-6;
+
+    #[rstest]
+    #[case(1013.25, 101_325.0, 759.999_891_726, 29.921_255_580)] // standard atmosphere
+    #[case(500.0, 50_000.0, 375.030_787_923, 14.764_991_651)]
+    #[case(0.0, 0.0, 0.0, 0.0)]
+    fn pressure_conversions(
+        #[case] hpa: f64,
+        #[case] pa: f64,
+        #[case] mmhg: f64,
+        #[case] inhg: f64,
+    ) {
+        assert!((hpa_to_pa(hpa) - pa).abs() < EPS);
+        assert!((hpa_to_mmhg(hpa) - mmhg).abs() < EPS);
+        assert!((hpa_to_inhg(hpa) - inhg).abs() < EPS);
+    }
+
+    #[rstest]
+    #[case(1000.0, 0.0, 1000.0)]
+    #[case(1000.0, 100.0, 1_011.938_365_533)]
+    #[case(950.0, 500.0, 1_008.349_541_093)]
+    #[case(1013.25, -400.0, 966.531_428_584)] // below sea level
+    fn sea_level_reduction(#[case] hpa: f64, #[case] altitude_m: f64, #[case] expected: f64) {
+        assert!((sea_level_pressure(hpa, altitude_m) - expected).abs() < EPS);
+    }
+
+    #[rstest]
+    #[case(25.0, 50.0, 50.0)] // reference temperature, unchanged
+    #[case(25.0, 0.0, 0.0)]
+    #[case(35.0, 50.0, 51.314_720_170)]
+    #[case(5.0, 80.0, 75.785_025_551)]
+    // Datasheet formulas at SO_RH = 2000: RH_linear = 64.9712, t1 + t2 * SO_RH = 0.17
+    #[case(50.0, 64.9712, 69.2212)]
+    #[case(0.0, 64.9712, 60.7212)]
+    fn humidity_compensation_sht1x(
+        #[case] temperature: f64,
+        #[case] humidity: f64,
+        #[case] expected: f64,
+    ) {
+        assert!((compensate_humidity_sht1x(temperature, humidity) - expected).abs() < EPS);
+    }
+
+    #[rstest]
+    #[case(24.300_000_000_000_004, 200.0, 24.3)]
+    #[case(4860.0 * 0.005, 200.0, 24.3)]
+    #[case(0.1 * 3.0, 10.0, 0.3)]
+    #[case(-1.460_000_000_000_000_1, 200.0, -1.46)]
+    #[case(11.652, 400.0, 11.6525)]
+    fn snap_to_resolution_removes_float_noise(
+        #[case] value: f64,
+        #[case] steps_per_unit: f64,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(
+            snap_to_resolution(value, steps_per_unit).to_bits(),
+            expected.to_bits()
+        );
+    }
+
+    // NWS heat index chart values, rounded to whole °F
+    #[rstest]
+    #[case::f90_rh70(32.222, 70.0, 106.0)]
+    #[case::f100_rh40(37.778, 40.0, 109.0)]
+    #[case::f86_rh50(30.0, 50.0, 88.0)]
+    #[case::f80_rh40(26.667, 40.0, 80.0)]
+    #[case::f104_rh10(40.0, 10.0, 98.0)]
+    fn heat_index_chart(#[case] temperature: f64, #[case] humidity: f64, #[case] fahrenheit: f64) {
+        let celsius = heat_index(temperature, humidity).unwrap();
+        assert!(
+            (celsius.mul_add(1.8, 32.0) - fahrenheit).abs() < 0.5,
+            "{celsius}°C"
+        );
+    }
+
+    // Environment Canada humidex table values, rounded to whole degrees
+    #[rstest]
+    #[case(30.0, 70.0, 41.0)]
+    #[case(35.0, 50.0, 45.0)]
+    fn humidex_table(#[case] temperature: f64, #[case] humidity: f64, #[case] expected: f64) {
+        let value = humidex(temperature, humidity).unwrap();
+        assert!((value - expected).abs() < 0.5, "{value}");
+    }
+
+    #[rstest]
+    #[case(26.6, 50.0)]
+    #[case(30.0, -1.0)]
+    #[case(30.0, 100.5)]
+    #[case(f64::NAN, 50.0)]
+    fn heat_index_undefined(#[case] temperature: f64, #[case] humidity: f64) {
+        assert_eq!(heat_index(temperature, humidity), None);
+    }
+
+    #[rstest]
+    #[case(19.9, 50.0)]
+    #[case(f64::NAN, 50.0)]
+    #[case(25.0, 101.0)]
+    #[case(25.0, f64::NAN)]
+    fn humidex_undefined(#[case] temperature: f64, #[case] humidity: f64) {
+        assert_eq!(humidex(temperature, humidity), None);
+    }
+
+    #[rstest]
+    #[case::v6_code_254("067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F", 65535.0)]
+    #[case::e1_max(
+        "E17FFF9C40FFFE27102710271027109C40FAFADC28F0000000FFFFFE3F0000000000CBB8334C884F",
+        144_284.0
+    )]
+    fn luminosity_maxima(#[case] hex_str: &str, #[case] expected: f64) {
+        let data = crate::RuuviData::decode(&hex::decode(hex_str).unwrap()).unwrap();
+        let lux = data.luminosity_lux().unwrap();
+        assert!((lux - expected).abs() < EPS, "{lux} != {expected}");
+    }
+
+    #[test]
+    fn luminosity_v5_unavailable() {
+        let data = crate::decode("0201061BFF99040512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")
+            .unwrap();
+        assert_eq!(data.luminosity_lux(), None);
+    }
+}
@@ -5,24 +5,42 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::encode;
 use crate::error::{DecodeError, Result};
+use crate::mac::{MacAddress, mac_hex_into};
+use crate::readings::{self, ReadingValue};
+use crate::sequence;
+use crate::units;
 
 /// Expected payload length for Data Format 5 in bytes
 pub const PAYLOAD_LENGTH: usize = 18;
 pub const PAYLOAD_WITH_MAC_LENGTH: usize = PAYLOAD_LENGTH + 6;
 
+/// MAC address reported by [`decode`] when the MAC bytes are all `0xFF`
+pub const INVALID_MAC_ADDRESS: &str = "invalid";
+/// MAC address reported by [`decode_partial`] when the payload has no MAC bytes
+pub const UNKNOWN_MAC_ADDRESS: &str = "unknown";
+
 /// Data Format 5 (`RAWv2`) structure
 ///
 /// This format contains all the sensor readings in a 24-byte payload
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
 pub struct DataFormatV5 {
     /// MAC address as lowercase hex string (without colons)
+    #[cfg_attr(
+        feature = "mac-colons",
+        serde(
+            serialize_with = "crate::mac::serialize_colon_uppercase",
+            deserialize_with = "crate::mac::deserialize_bare"
+        )
+    )]
     pub mac_address: String,
     /// Temperature in Celsius (-163.835 to +163.835°C, 0.005°C resolution)
     pub temperature: Option<f64>,
     /// Humidity in % (0 to 163.835%, 0.0025% resolution)
     pub humidity: Option<f64>,
-    /// Pressure in hPa (500-1155.35 hPa, 0.01 hPa resolution)
+    /// Pressure in hPa (500.00 to 1155.34 hPa, 0.01 hPa resolution)
     pub pressure: Option<f64>,
     /// Acceleration X-axis in millig (-32767 to +32767 mg, 1 mg resolution)
     pub acceleration_x: Option<i16>,
@@ -40,6 +58,565 @@ pub struct DataFormatV5 {
     pub measurement_sequence: Option<u16>,
 }
 
+/// Milli-g per standard gravity
+const MG_PER_G: f64 = 1000.0;
+
+/// Acceleration vector in mg, with all three axes available
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Acceleration {
+    /// X-axis in mg
+    pub x: i16,
+    /// Y-axis in mg
+    pub y: i16,
+    /// Z-axis in mg
+    pub z: i16,
+}
+
+impl Acceleration {
+    /// Length of the vector in mg
+    #[must_use]
+    pub fn magnitude(&self) -> f64 {
+        f64::hypot(
+            f64::hypot(f64::from(self.x), f64::from(self.y)),
+            f64::from(self.z),
+        )
+    }
+
+    /// Length of the vector in g, about 1.0 for a sensor at rest
+    #[must_use]
+    pub fn magnitude_g(&self) -> f64 {
+        self.magnitude() / MG_PER_G
+    }
+
+    /// The vector in g per axis
+    #[must_use]
+    pub fn to_g(&self) -> [f64; 3] {
+        [self.x, self.y, self.z].map(|axis| f64::from(axis) / MG_PER_G)
+    }
+}
+
+/// Unscaled Data Format 5 field values, as transmitted on air
+///
+/// Each field is `None` exactly when the matching field of [`DataFormatV5`] is `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawV5 {
+    /// Temperature in 0.005°C steps
+    pub temperature: Option<i16>,
+    /// Humidity in 0.0025% steps
+    pub humidity: Option<u16>,
+    /// Pressure in 1 Pa steps, offset by -50000 Pa
+    pub pressure: Option<u16>,
+    /// Acceleration X-axis in mg
+    pub acceleration_x: Option<i16>,
+    /// Acceleration Y-axis in mg
+    pub acceleration_y: Option<i16>,
+    /// Acceleration Z-axis in mg
+    pub acceleration_z: Option<i16>,
+    /// Battery voltage in 1 mV steps above 1600 mV (11 bits)
+    pub battery_voltage: Option<u16>,
+    /// TX power in 2 dBm steps above -40 dBm (5 bits)
+    pub tx_power: Option<u8>,
+    /// Movement counter
+    pub movement_counter: Option<u8>,
+    /// Measurement sequence number
+    pub measurement_sequence: Option<u16>,
+}
+
+impl DataFormatV5 {
+    /// Start building a `DataFormatV5` by hand, e.g. for tests or simulated sensors
+    #[must_use]
+    pub fn builder() -> DataFormatV5Builder {
+        DataFormatV5Builder::default()
+    }
+
+    /// Get the unscaled field values this reading was decoded from
+    ///
+    /// Battery voltage and TX power the 11 and 5 bit fields cannot hold, e.g.
+    /// set through the builder, come back as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::v5::decode;
+    ///
+    /// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+    /// let raw = decode(&bytes).unwrap().raw();
+    /// assert_eq!(raw.temperature, Some(0x12FC));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn raw(&self) -> RawV5 {
+        RawV5 {
+            temperature: self.temperature.map(|t| (t / 0.005).round() as i16),
+            humidity: self.humidity.map(|h| (h / 0.0025).round() as u16),
+            pressure: self
+                .pressure
+                .map(|p| (units::hpa_to_pa(p) - 50000.0).round() as u16),
+            acceleration_x: self.acceleration_x,
+            acceleration_y: self.acceleration_y,
+            acceleration_z: self.acceleration_z,
+            battery_voltage: self
+                .battery_voltage
+                .and_then(|v| v.checked_sub(1600))
+                .filter(|r| *r <= 2046),
+            tx_power: self
+                .tx_power
+                .filter(|p| (-40..=20).contains(p))
+                .map(|p| (p / 2 + 20).cast_unsigned()),
+            movement_counter: self.movement_counter,
+            measurement_sequence: self.measurement_sequence,
+        }
+    }
+
+    /// Compare with `other`, allowing float fields to differ by up to `eps`
+    ///
+    /// Integer fields and the MAC address must match exactly, and a float field
+    /// that is `None` only equals `None`. Useful when a reading went through a
+    /// lossy round trip, e.g. encode and decode, where exact `==` is brittle.
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        let close = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => (a - b).abs() <= eps,
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        self.mac_address == other.mac_address
+            && close(self.temperature, other.temperature)
+            && close(self.humidity, other.humidity)
+            && close(self.pressure, other.pressure)
+            && self.acceleration_x == other.acceleration_x
+            && self.acceleration_y == other.acceleration_y
+            && self.acceleration_z == other.acceleration_z
+            && self.battery_voltage == other.battery_voltage
+            && self.tx_power == other.tx_power
+            && self.movement_counter == other.movement_counter
+            && self.measurement_sequence == other.measurement_sequence
+    }
+
+    /// Whether `mac_address` holds a real MAC address
+    ///
+    /// `false` for the [`INVALID_MAC_ADDRESS`] and [`UNKNOWN_MAC_ADDRESS`]
+    /// placeholders, or anything else that is not 12 hex digits.
+    #[must_use]
+    pub fn mac_is_valid(&self) -> bool {
+        self.mac_address.len() == 12 && self.mac_address.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    /// The MAC address as a [`MacAddress`], `None` for the placeholders
+    #[must_use]
+    pub fn mac(&self) -> Option<MacAddress> {
+        self.mac_address.parse().ok()
+    }
+
+    /// Available fields as `(name, value)` pairs, skipping `None` fields
+    ///
+    /// Names match the struct fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::{ReadingValue, v5::decode};
+    ///
+    /// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+    /// let data = decode(&bytes).unwrap();
+    /// let (name, value) = data.readings().nth(1).unwrap();
+    /// assert_eq!((name, value), ("temperature", ReadingValue::F64(24.3)));
+    /// ```
+    pub fn readings(&self) -> impl Iterator<Item = (&'static str, ReadingValue)> {
+        readings::available([
+            (
+                "mac_address",
+                Some(ReadingValue::String(self.mac_address.clone())),
+            ),
+            ("temperature", self.temperature.map(ReadingValue::F64)),
+            ("humidity", self.humidity.map(ReadingValue::F64)),
+            ("pressure", self.pressure.map(ReadingValue::F64)),
+            ("acceleration_x", self.acceleration_x.map(ReadingValue::I16)),
+            ("acceleration_y", self.acceleration_y.map(ReadingValue::I16)),
+            ("acceleration_z", self.acceleration_z.map(ReadingValue::I16)),
+            (
+                "battery_voltage",
+                self.battery_voltage.map(ReadingValue::U16),
+            ),
+            ("tx_power", self.tx_power.map(ReadingValue::I8)),
+            (
+                "movement_counter",
+                self.movement_counter.map(ReadingValue::U8),
+            ),
+            (
+                "measurement_sequence",
+                self.measurement_sequence.map(ReadingValue::U16),
+            ),
+        ])
+    }
+
+    /// Whether `other` reports the same physical conditions
+    ///
+    /// Compares the sensor fields only, ignoring `mac_address`,
+    /// `movement_counter` and `measurement_sequence`, so two advertisements of
+    /// an unchanged environment match even though their counters differ.
+    #[must_use]
+    pub fn same_measurement(&self, other: &Self) -> bool {
+        self.temperature == other.temperature
+            && self.humidity == other.humidity
+            && self.pressure == other.pressure
+            && self.acceleration_x == other.acceleration_x
+            && self.acceleration_y == other.acceleration_y
+            && self.acceleration_z == other.acceleration_z
+            && self.battery_voltage == other.battery_voltage
+            && self.tx_power == other.tx_power
+    }
+
+    /// Whether at least one sensor field holds a reading
+    ///
+    /// `false` for all-sentinel payloads a tag broadcasts before its sensors
+    /// have produced data. Transmit power and measurement sequence are
+    /// advertisement metadata and do not count as readings.
+    #[must_use]
+    pub fn has_any_reading(&self) -> bool {
+        self.temperature.is_some()
+            || self.humidity.is_some()
+            || self.pressure.is_some()
+            || self.acceleration_x.is_some()
+            || self.acceleration_y.is_some()
+            || self.acceleration_z.is_some()
+            || self.battery_voltage.is_some()
+            || self.movement_counter.is_some()
+    }
+
+    /// Whether the sensor fields hold the pattern of an all-zero payload
+    ///
+    /// Freshly booted tags, and firmware that advertises before its first
+    /// measurement, may send zeroed sensor bytes instead of the "not
+    /// available" sentinels. Those decode to 0°C, 0% humidity, 500 hPa (raw
+    /// pressure 0) and zero acceleration on every axis. Each value is possible
+    /// on its own, but a resting tag always measures gravity on some axis and
+    /// 500 hPa is far below any inhabited altitude, so together they are
+    /// treated as uninitialized. Battery, transmit power, movement counter and
+    /// sequence are not considered.
+    #[must_use]
+    pub fn looks_like_uninitialized(&self) -> bool {
+        self.temperature == Some(0.0)
+            && self.humidity == Some(0.0)
+            && self.pressure == Some(500.0)
+            && self.acceleration_x == Some(0)
+            && self.acceleration_y == Some(0)
+            && self.acceleration_z == Some(0)
+    }
+
+    /// Acceleration vector, `None` unless all three axes are available
+    #[must_use]
+    pub fn acceleration(&self) -> Option<Acceleration> {
+        Some(Acceleration {
+            x: self.acceleration_x?,
+            y: self.acceleration_y?,
+            z: self.acceleration_z?,
+        })
+    }
+
+    /// Acceleration per axis in g, `None` unless all three axes are available
+    #[must_use]
+    pub fn acceleration_g(&self) -> Option<(f64, f64, f64)> {
+        let [x, y, z] = self.acceleration()?.to_g();
+        Some((x, y, z))
+    }
+
+    /// Whether the sensor is in motion rather than at rest
+    ///
+    /// At rest the accelerometer only measures gravity, a magnitude of 1000 mg.
+    /// The sensor counts as moving when the magnitude deviates from that by more
+    /// than `gravity_tolerance_mg` in either direction; the sign of the tolerance
+    /// is ignored.
+    ///
+    /// # Returns
+    ///
+    /// `None` unless all three acceleration axes are available
+    #[must_use]
+    pub fn is_moving(&self, gravity_tolerance_mg: i16) -> Option<bool> {
+        let deviation = (self.acceleration()?.magnitude() - 1000.0).abs();
+        Some(deviation > f64::from(gravity_tolerance_mg.unsigned_abs()))
+    }
+
+    /// Heat index in Celsius, see [`units::heat_index`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range the index is defined for.
+    #[must_use]
+    pub fn heat_index_c(&self) -> Option<f64> {
+        units::heat_index(self.temperature?, self.humidity?)
+    }
+
+    /// Humidex, see [`units::humidex`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range humidex is defined for.
+    #[must_use]
+    pub fn humidex(&self) -> Option<f64> {
+        units::humidex(self.temperature?, self.humidity?)
+    }
+
+    /// Copy with temperature, humidity and pressure snapped to their resolution
+    ///
+    /// Decoding multiplies the raw value by a fractional step, which leaves
+    /// noise like `24.300000000000004`; the rounded copy holds `24.3`.
+    #[must_use]
+    pub fn rounded(&self) -> Self {
+        Self {
+            temperature: self
+                .temperature
+                .map(|t| units::snap_to_resolution(t, 200.0)),
+            humidity: self.humidity.map(|h| units::snap_to_resolution(h, 400.0)),
+            pressure: self.pressure.map(|p| units::snap_to_resolution(p, 100.0)),
+            ..self.clone()
+        }
+    }
+
+    /// Pressure in Pa, rounded to the 1 Pa resolution of the format
+    ///
+    /// Rounding removes the float noise of converting the hPa `pressure` field
+    /// back, so the raw range edges come out as exactly 50000 and 115534 Pa.
+    #[must_use]
+    pub fn pressure_pa(&self) -> Option<f64> {
+        self.pressure.map(|hpa| units::hpa_to_pa(hpa).round())
+    }
+
+    /// Pressure in hPa, same as the `pressure` field
+    #[must_use]
+    pub fn pressure_hpa(&self) -> Option<f64> {
+        self.pressure
+    }
+
+    /// Pressure in mmHg
+    #[must_use]
+    pub fn pressure_mmhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_mmhg)
+    }
+
+    /// Pressure in inHg
+    #[must_use]
+    pub fn pressure_inhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_inhg)
+    }
+
+    /// Pressure reduced to mean sea level in hPa, for a station at `altitude_m` metres
+    ///
+    /// See [`units::sea_level_pressure`].
+    #[must_use]
+    pub fn pressure_at_sea_level(&self, altitude_m: f64) -> Option<f64> {
+        self.pressure
+            .map(|hpa| units::sea_level_pressure(hpa, altitude_m))
+    }
+
+    /// Check whether this reading was measured after `other`, handling counter wraparound
+    ///
+    /// Returns `None` when either measurement sequence is unavailable.
+    #[must_use]
+    pub fn is_newer_than(&self, other: &Self) -> Option<bool> {
+        let current = self.measurement_sequence?;
+        let other = other.measurement_sequence?;
+        Some(sequence::is_newer(
+            u32::from(current),
+            u32::from(other),
+            sequence::V5_SEQUENCE_MODULUS,
+        ))
+    }
+
+    /// Serialize to the JSON schema used by Ruuvi Station and its backends
+    ///
+    /// Fields are `temperature` (°C), `humidity` (%), `pressure` (hPa),
+    /// `accelerationX`/`Y`/`Z` (mg), `voltage` (V), `txPower` (dBm),
+    /// `movementCounter` and `measurementSequenceNumber`. Unavailable readings
+    /// are omitted rather than written as `null`.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: every field serializes to a valid JSON value
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_station_json(&self) -> String {
+        let station = StationV5 {
+            temperature: self.temperature,
+            humidity: self.humidity,
+            pressure: self.pressure,
+            acceleration_x: self.acceleration_x,
+            acceleration_y: self.acceleration_y,
+            acceleration_z: self.acceleration_z,
+            voltage: self.battery_voltage.map(|mv| f64::from(mv) / 1000.0),
+            tx_power: self.tx_power,
+            movement_counter: self.movement_counter,
+            measurement_sequence_number: self.measurement_sequence,
+        };
+        serde_json::to_string(&station).expect("Station reading serializes to JSON")
+    }
+}
+
+/// Ruuvi Station field names and units for a Data Format 5 reading
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StationV5 {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    humidity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pressure: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    acceleration_x: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    acceleration_y: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    acceleration_z: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voltage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_power: Option<i8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    movement_counter: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    measurement_sequence_number: Option<u16>,
+}
+
+/// Fluent builder for [`DataFormatV5`]
+///
+/// Unset readings are `None` and an unset MAC address is `"invalid"`, matching
+/// what [`decode`] produces for unavailable values.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v5::DataFormatV5;
+///
+/// let data = DataFormatV5::builder()
+///     .temperature(24.3)
+///     .humidity(53.49)
+///     .mac_address("CB:B8:33:4C:88:4F")
+///     .build();
+///
+/// assert_eq!(data.temperature, Some(24.3));
+/// assert_eq!(data.pressure, None);
+/// assert_eq!(data.mac_address, "cbb8334c884f");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DataFormatV5Builder {
+    mac_address: Option<String>,
+    temperature: Option<f64>,
+    humidity: Option<f64>,
+    pressure: Option<f64>,
+    acceleration_x: Option<i16>,
+    acceleration_y: Option<i16>,
+    acceleration_z: Option<i16>,
+    battery_voltage: Option<u16>,
+    tx_power: Option<i8>,
+    movement_counter: Option<u8>,
+    measurement_sequence: Option<u16>,
+}
+
+impl DataFormatV5Builder {
+    /// Set the MAC address, either bare hex or separated by `:` or `-`
+    #[must_use]
+    pub fn mac_address(mut self, mac_address: &str) -> Self {
+        self.mac_address = Some(
+            mac_address
+                .chars()
+                .filter(|c| *c != ':' && *c != '-')
+                .map(|c| c.to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Set the temperature in Celsius
+    #[must_use]
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the humidity in %
+    #[must_use]
+    pub fn humidity(mut self, humidity: f64) -> Self {
+        self.humidity = Some(humidity);
+        self
+    }
+
+    /// Set the pressure in hPa
+    #[must_use]
+    pub fn pressure(mut self, pressure: f64) -> Self {
+        self.pressure = Some(pressure);
+        self
+    }
+
+    /// Set the X-axis acceleration in mg
+    #[must_use]
+    pub fn acceleration_x(mut self, acceleration_x: i16) -> Self {
+        self.acceleration_x = Some(acceleration_x);
+        self
+    }
+
+    /// Set the Y-axis acceleration in mg
+    #[must_use]
+    pub fn acceleration_y(mut self, acceleration_y: i16) -> Self {
+        self.acceleration_y = Some(acceleration_y);
+        self
+    }
+
+    /// Set the Z-axis acceleration in mg
+    #[must_use]
+    pub fn acceleration_z(mut self, acceleration_z: i16) -> Self {
+        self.acceleration_z = Some(acceleration_z);
+        self
+    }
+
+    /// Set the battery voltage in mV
+    #[must_use]
+    pub fn battery_voltage(mut self, battery_voltage: u16) -> Self {
+        self.battery_voltage = Some(battery_voltage);
+        self
+    }
+
+    /// Set the TX power in dBm
+    #[must_use]
+    pub fn tx_power(mut self, tx_power: i8) -> Self {
+        self.tx_power = Some(tx_power);
+        self
+    }
+
+    /// Set the movement counter
+    #[must_use]
+    pub fn movement_counter(mut self, movement_counter: u8) -> Self {
+        self.movement_counter = Some(movement_counter);
+        self
+    }
+
+    /// Set the measurement sequence number
+    #[must_use]
+    pub fn measurement_sequence(mut self, measurement_sequence: u16) -> Self {
+        self.measurement_sequence = Some(measurement_sequence);
+        self
+    }
+
+    /// Build the `DataFormatV5`
+    #[must_use]
+    pub fn build(self) -> DataFormatV5 {
+        DataFormatV5 {
+            mac_address: self
+                .mac_address
+                .unwrap_or_else(|| INVALID_MAC_ADDRESS.to_string()),
+            temperature: self.temperature,
+            humidity: self.humidity,
+            pressure: self.pressure,
+            acceleration_x: self.acceleration_x,
+            acceleration_y: self.acceleration_y,
+            acceleration_z: self.acceleration_z,
+            battery_voltage: self.battery_voltage,
+            tx_power: self.tx_power,
+            movement_counter: self.movement_counter,
+            measurement_sequence: self.measurement_sequence,
+        }
+    }
+}
+
 /// Decode Data Format 5 payload from raw bytes
 ///
 /// # Arguments
@@ -65,14 +642,6 @@ pub struct DataFormatV5 {
 ///
 /// * `Err(DecodeError::InvalidLength)` - Input length is not 24 bytes
 /// * `Err(DecodeError::UnsupportedFormat)` - Format identifier is not 5
-/// * `Err(DecodeError::InvalidTemperature)` - Temperature value is invalid
-/// * `Err(DecodeError::InvalidHumidity)` - Humidity value is invalid
-/// * `Err(DecodeError::InvalidPressure)` - Pressure value is invalid
-/// * `Err(DecodeError::InvalidAcceleration)` - Acceleration value is invalid
-/// * `Err(DecodeError::InvalidPowerInfo)` - Power information is invalid
-/// * `Err(DecodeError::InvalidMovementCounter)` - Movement counter is invalid
-/// * `Err(DecodeError::InvalidMeasurementSequence)` - Measurement sequence is invalid
-/// * `Err(DecodeError::InvalidMacAddress)` - MAC address is invalid
 pub fn decode(bytes: &[u8]) -> Result<DataFormatV5> {
     // Validate input length
     if bytes.len() != PAYLOAD_WITH_MAC_LENGTH {
@@ -82,22 +651,325 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatV5> {
         ));
     }
 
+    decode_fields(bytes, decode_mac_address(&bytes[18..24]))
+}
+
+/// Borrowed view of a Data Format 5 payload that decodes fields on access
+///
+/// For pipelines that only look at a few fields: creating the view does not
+/// allocate, unlike [`decode`] which builds the MAC address `String`. The
+/// accessors return the same values as the fields of [`DataFormatV5`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataFormatV5View<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> DataFormatV5View<'a> {
+    /// Temperature in Celsius
+    #[must_use]
+    pub fn temperature(&self) -> Option<f64> {
+        self.field(decode_temperature, 1, 2)
+    }
+
+    /// Humidity in %
+    #[must_use]
+    pub fn humidity(&self) -> Option<f64> {
+        self.field(decode_humidity, 3, 2)
+    }
+
+    /// Pressure in hPa
+    #[must_use]
+    pub fn pressure(&self) -> Option<f64> {
+        self.field(decode_pressure, 5, 2)
+    }
+
+    /// Acceleration X-axis in mg
+    #[must_use]
+    pub fn acceleration_x(&self) -> Option<i16> {
+        self.field(decode_acceleration, 7, 2)
+    }
+
+    /// Acceleration Y-axis in mg
+    #[must_use]
+    pub fn acceleration_y(&self) -> Option<i16> {
+        self.field(decode_acceleration, 9, 2)
+    }
+
+    /// Acceleration Z-axis in mg
+    #[must_use]
+    pub fn acceleration_z(&self) -> Option<i16> {
+        self.field(decode_acceleration, 11, 2)
+    }
+
+    /// Battery voltage in mV
+    #[must_use]
+    pub fn battery_voltage(&self) -> Option<u16> {
+        self.power_info().0
+    }
+
+    /// TX power in dBm
+    #[must_use]
+    pub fn tx_power(&self) -> Option<i8> {
+        self.power_info().1
+    }
+
+    /// Movement counter
+    #[must_use]
+    pub fn movement_counter(&self) -> Option<u8> {
+        self.field(decode_movement_counter, 15, 1)
+    }
+
+    /// Measurement sequence number
+    #[must_use]
+    pub fn measurement_sequence(&self) -> Option<u16> {
+        self.field(decode_measurement_sequence, 16, 2)
+    }
+
+    /// Raw MAC address bytes, all `0xFF` when unavailable
+    #[must_use]
+    pub fn mac_address_bytes(&self) -> &'a [u8] {
+        &self.bytes[PAYLOAD_LENGTH..PAYLOAD_WITH_MAC_LENGTH]
+    }
+
+    /// Decode every field into an owned [`DataFormatV5`], same as [`decode`]
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: [`view`] already checked the length and format byte
+    #[must_use]
+    pub fn to_owned(&self) -> DataFormatV5 {
+        decode(self.bytes).expect("view was validated by v5::view")
+    }
+
+    /// Decode a field, which cannot fail as [`view`] checked the length
+    fn field<T>(
+        &self,
+        decode: fn(&[u8], usize) -> Result<Option<T>>,
+        offset: usize,
+        len: usize,
+    ) -> Option<T> {
+        decode(&self.bytes[offset..offset + len], offset)
+            .ok()
+            .flatten()
+    }
+
+    fn power_info(&self) -> (Option<u16>, Option<i8>) {
+        decode_power_info(&self.bytes[13..15], 13).unwrap_or_default()
+    }
+}
+
+/// Borrow a Data Format 5 payload as a [`DataFormatV5View`] without decoding it
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v5;
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// let view = v5::view(&bytes).unwrap();
+/// assert_eq!(view.temperature(), Some(24.3));
+/// assert_eq!(view.to_owned(), v5::decode(&bytes).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// * `Err(DecodeError::InvalidLength)` - Input length is not 24 bytes
+/// * `Err(DecodeError::UnsupportedFormat)` - Format identifier is not 5
+pub fn view(bytes: &[u8]) -> Result<DataFormatV5View<'_>> {
+    if bytes.len() != PAYLOAD_WITH_MAC_LENGTH {
+        return Err(DecodeError::invalid_length(
+            PAYLOAD_WITH_MAC_LENGTH,
+            bytes.len(),
+        ));
+    }
+    if bytes[0] != 5 {
+        return Err(DecodeError::UnsupportedFormat(bytes[0]));
+    }
+
+    Ok(DataFormatV5View { bytes })
+}
+
+/// Decode a Data Format 5 payload, rejecting an unavailable MAC address
+///
+/// Unlike [`decode`], which reports an all `0xFF` MAC address as
+/// [`INVALID_MAC_ADDRESS`], this fails so a placeholder can never be mistaken
+/// for a real sensor address.
+///
+/// # Errors
+///
+/// * `Err(DecodeError::InvalidMacAddress)` - MAC address bytes are all `0xFF`
+/// * Any error returned by [`decode`]
+pub fn decode_strict(bytes: &[u8]) -> Result<DataFormatV5> {
+    let data = decode(bytes)?;
+    parse_mac_address(&bytes[18..24])?;
+    Ok(data)
+}
+
+/// Decode a Data Format 5 payload that may be missing its trailing MAC address
+///
+/// Some BLE stacks truncate the advertisement and drop the MAC bytes. This
+/// accepts both the 18 byte payload without MAC, whose `mac_address` is then
+/// `"unknown"`, and the full 24 byte payload, decoded like [`decode`].
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v5::decode_partial;
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CD").unwrap();
+/// let result = decode_partial(&bytes).unwrap();
+/// assert_eq!(result.temperature, Some(24.3));
+/// assert_eq!(result.mac_address, "unknown");
+/// ```
+///
+/// # Errors
+///
+/// * `Err(DecodeError::InvalidLength)` - Input length is neither 18 nor 24 bytes
+/// * `Err(DecodeError::UnsupportedFormat)` - Format identifier is not 5
+pub fn decode_partial(bytes: &[u8]) -> Result<DataFormatV5> {
+    match bytes.len() {
+        PAYLOAD_WITH_MAC_LENGTH => decode(bytes),
+        PAYLOAD_LENGTH => decode_fields(bytes, UNKNOWN_MAC_ADDRESS.to_string()),
+        len => Err(DecodeError::invalid_length(PAYLOAD_LENGTH, len)),
+    }
+}
+
+/// Encode a reading back into a 24 byte Data Format 5 payload
+///
+/// This is the inverse of [`decode`]: `None` fields become their "not
+/// available" sentinels and the [`INVALID_MAC_ADDRESS`] and
+/// [`UNKNOWN_MAC_ADDRESS`] placeholders become an all `0xFF` MAC address.
+/// Float fields are rounded to the resolution of the format.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v5::{decode, encode};
+///
+/// let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+/// assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+/// ```
+///
+/// # Errors
+///
+/// Fails with the field specific error, e.g. `DecodeError::InvalidTemperature`,
+/// when a value cannot be represented in the format, or with
+/// `DecodeError::InvalidMacAddress` when `mac_address` is not 12 hex digits.
+pub fn encode(data: &DataFormatV5) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(PAYLOAD_WITH_MAC_LENGTH);
+    bytes.push(5);
+    bytes.extend(encode::temperature(data.temperature)?);
+    bytes.extend(encode::humidity(data.humidity)?);
+    bytes.extend(encode::pressure(data.pressure)?);
+    for axis in [
+        data.acceleration_x,
+        data.acceleration_y,
+        data.acceleration_z,
+    ] {
+        bytes.extend(encode_acceleration(axis)?);
+    }
+    bytes.extend(encode_power_info(data.battery_voltage, data.tx_power)?);
+    bytes.push(match data.movement_counter {
+        Some(255) => {
+            return Err(DecodeError::InvalidMovementCounter(
+                "255 is reserved".into(),
+            ));
+        }
+        Some(counter) => counter,
+        None => 255,
+    });
+    bytes.extend(
+        match data.measurement_sequence {
+            Some(u16::MAX) => {
+                return Err(DecodeError::InvalidMeasurementSequence(
+                    "65535 is reserved".into(),
+                ));
+            }
+            Some(sequence) => sequence,
+            None => u16::MAX,
+        }
+        .to_be_bytes(),
+    );
+    bytes.extend(match data.mac_address.as_str() {
+        INVALID_MAC_ADDRESS | UNKNOWN_MAC_ADDRESS => [0xFF; 6],
+        mac_address => encode::mac_address(mac_address)?,
+    });
+    Ok(bytes)
+}
+
+/// Encode acceleration in mg, `None` as `0x8000`
+fn encode_acceleration(value: Option<i16>) -> Result<[u8; 2]> {
+    match value {
+        Some(i16::MIN) => Err(DecodeError::InvalidAcceleration(
+            "-32768 is reserved".into(),
+        )),
+        Some(mg) => Ok(mg.to_be_bytes()),
+        None => Ok(i16::MIN.to_be_bytes()),
+    }
+}
+
+/// Encode battery voltage and TX power into 11 + 5 bits, `None` as all ones
+fn encode_power_info(battery_voltage: Option<u16>, tx_power: Option<i8>) -> Result<[u8; 2]> {
+    if let Some(mv) = battery_voltage.filter(|mv| !(1600..=3646).contains(mv)) {
+        return Err(DecodeError::InvalidPowerInfo(format!(
+            "battery voltage {mv} mV is outside 1600..=3646 mV"
+        )));
+    }
+    if let Some(dbm) = tx_power.filter(|dbm| !(-40..=20).contains(dbm) || dbm % 2 != 0) {
+        return Err(DecodeError::InvalidPowerInfo(format!(
+            "TX power {dbm} dBm is not an even value in -40..=20 dBm"
+        )));
+    }
+    Ok(pack_power_info(battery_voltage, tx_power).to_be_bytes())
+}
+
+/// Pack battery voltage and TX power into the 16-bit power info field
+///
+/// Inverse of the decoder: the upper 11 bits hold `voltage_mv - 1600` and the
+/// lower 5 bits `(tx_dbm + 40) / 2`. `None`, and any value the field cannot
+/// represent (voltage outside 1600..=3646 mV, TX power outside -40..=20 dBm or
+/// odd), packs as the 2047 and 31 "not available" sentinels. Use [`encode`] to
+/// reject such values instead.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v5::pack_power_info;
+///
+/// assert_eq!(pack_power_info(Some(2977), Some(4)), 0xAC36);
+/// assert_eq!(pack_power_info(None, None), 0xFFFF);
+/// ```
+#[must_use]
+#[allow(clippy::cast_sign_loss)]
+pub fn pack_power_info(voltage_mv: Option<u16>, tx_dbm: Option<i8>) -> u16 {
+    let battery_raw = match voltage_mv {
+        Some(mv @ 1600..=3646) => mv - 1600,
+        _ => 2047,
+    };
+    let tx_power_raw = match tx_dbm {
+        Some(dbm @ -40..=20) if dbm % 2 == 0 => (dbm / 2 + 20) as u16,
+        _ => 31,
+    };
+    (battery_raw << 5) | tx_power_raw
+}
+
+/// Decode the 18 byte sensor payload, `bytes` must be at least that long
+fn decode_fields(bytes: &[u8], mac_address: String) -> Result<DataFormatV5> {
     // Validate format identifier
     if bytes[0] != 5 {
         return Err(DecodeError::UnsupportedFormat(bytes[0]));
     }
 
     // Extract all fields
-    let temperature = decode_temperature(&bytes[1..3])?;
-    let humidity = decode_humidity(&bytes[3..5])?;
-    let pressure = decode_pressure(&bytes[5..7])?;
-    let acceleration_x = decode_acceleration(&bytes[7..9])?;
-    let acceleration_y = decode_acceleration(&bytes[9..11])?;
-    let acceleration_z = decode_acceleration(&bytes[11..13])?;
-    let (battery_voltage, tx_power) = decode_power_info(&bytes[13..15])?;
-    let movement_counter = decode_movement_counter(bytes[15]);
-    let measurement_sequence = decode_measurement_sequence(&bytes[16..18])?;
-    let mac_address = decode_mac_address(&bytes[18..24]);
+    let temperature = decode_temperature(&bytes[1..3], 1)?;
+    let humidity = decode_humidity(&bytes[3..5], 3)?;
+    let pressure = decode_pressure(&bytes[5..7], 5)?;
+    let acceleration_x = decode_acceleration(&bytes[7..9], 7)?;
+    let acceleration_y = decode_acceleration(&bytes[9..11], 9)?;
+    let acceleration_z = decode_acceleration(&bytes[11..13], 11)?;
+    let (battery_voltage, tx_power) = decode_power_info(&bytes[13..15], 13)?;
+    let movement_counter = decode_movement_counter(&bytes[15..16], 15)?;
+    let measurement_sequence = decode_measurement_sequence(&bytes[16..18], 16)?;
 
     Ok(DataFormatV5 {
         mac_address,
@@ -114,17 +986,30 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatV5> {
     })
 }
 
+/// Read an `N` byte field starting at `offset` in the payload
+///
+/// `offset` is only used to report where a malformed field sits in the
+/// payload, through the field specific `error` variant.
+fn field_bytes<const N: usize>(
+    bytes: &[u8],
+    offset: usize,
+    error: fn(String) -> DecodeError,
+) -> Result<[u8; N]> {
+    bytes.try_into().map_err(|_| {
+        error(format!(
+            "bytes {offset}..{} must be {N} bytes, got {}",
+            offset + N,
+            bytes.len()
+        ))
+    })
+}
+
 /// Decode temperature from 2 bytes
 /// Range: -163.835°C to +163.835°C in 0.005°C increments
 /// Invalid value: 0x8000 (-32768)
-fn decode_temperature(bytes: &[u8]) -> Result<Option<f64>> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Temperature field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = i16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_temperature(bytes: &[u8], offset: usize) -> Result<Option<f64>> {
+    let raw_value =
+        i16::from_be_bytes(field_bytes(bytes, offset, DecodeError::InvalidTemperature)?);
 
     if raw_value == i16::MIN {
         // 0x8000 = invalid/not available
@@ -139,14 +1024,8 @@ fn decode_temperature(bytes: &[u8]) -> Result<Option<f64>> {
 /// Decode humidity from 2 bytes
 /// Range: 0% to 163.835% in 0.0025% increments
 /// Invalid value: 65535
-fn decode_humidity(bytes: &[u8]) -> Result<Option<f64>> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Humidity field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = u16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_humidity(bytes: &[u8], offset: usize) -> Result<Option<f64>> {
+    let raw_value = u16::from_be_bytes(field_bytes(bytes, offset, DecodeError::InvalidHumidity)?);
 
     if raw_value == 65535 {
         // 0xFFFF = invalid/not available
@@ -158,39 +1037,31 @@ fn decode_humidity(bytes: &[u8]) -> Result<Option<f64>> {
     }
 }
 
-/// Decode pressure from 2 bytes
+/// Decode pressure from 2 bytes, in hPa
 /// Range: 50000Pa to 115534Pa in 1Pa increments (with -50000Pa offset)
 /// Invalid value: 65535
-fn decode_pressure(bytes: &[u8]) -> Result<Option<f64>> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Pressure field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = u16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_pressure(bytes: &[u8], offset: usize) -> Result<Option<f64>> {
+    let raw_value = u16::from_be_bytes(field_bytes(bytes, offset, DecodeError::InvalidPressure)?);
 
     if raw_value == 65535 {
         // 0xFFFF = invalid/not available
         Ok(None)
     } else {
         // Add offset of 50000Pa
-        let pressure = f64::from(raw_value) + 50000.0;
-        Ok(Some(pressure))
+        let pa = i32::from(raw_value) + 50000;
+        Ok(Some(f64::from(pa) / 100.0)) // Convert Pa to hPa
     }
 }
 
 /// Decode acceleration from 2 bytes
 /// Range: -32767 to +32767 mG
 /// Invalid value: -32768 (0x8000)
-fn decode_acceleration(bytes: &[u8]) -> Result<Option<i16>> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Acceleration field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = i16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_acceleration(bytes: &[u8], offset: usize) -> Result<Option<i16>> {
+    let raw_value = i16::from_be_bytes(field_bytes(
+        bytes,
+        offset,
+        DecodeError::InvalidAcceleration,
+    )?);
 
     if raw_value == i16::MIN {
         // 0x8000 = invalid/not available
@@ -204,14 +1075,8 @@ fn decode_acceleration(bytes: &[u8]) -> Result<Option<i16>> {
 /// Battery voltage: 11 bits (1600mV to 3647mV)
 /// TX power: 5 bits (-40dBm to +20dBm in 2dBm steps)
 /// Invalid values: 2047 for battery, 31 for TX power
-fn decode_power_info(bytes: &[u8]) -> Result<(Option<u16>, Option<i8>)> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Power info field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = u16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_power_info(bytes: &[u8], offset: usize) -> Result<(Option<u16>, Option<i8>)> {
+    let raw_value = u16::from_be_bytes(field_bytes(bytes, offset, DecodeError::InvalidPowerInfo)?);
 
     // Battery voltage: upper 11 bits
     let battery_raw = (raw_value >> 5) & 0x07FF; // Extract bits 15-5
@@ -226,7 +1091,9 @@ fn decode_power_info(bytes: &[u8]) -> Result<(Option<u16>, Option<i8>)> {
     let tx_power = if tx_power_raw == 31 {
         None // Invalid/not available
     } else {
-        Some(-40 + (tx_power_raw.cast_signed()) * 2) // -40dBm + (value * 2dBm)
+        // -40dBm + (value * 2dBm), widened so the arithmetic cannot overflow;
+        // raw 0..=30 maps to -40..=20 dBm, which always fits an i8
+        i8::try_from(i16::from(tx_power_raw) * 2 - 40).ok()
     };
 
     Ok((battery_voltage, tx_power))
@@ -235,25 +1102,25 @@ fn decode_power_info(bytes: &[u8]) -> Result<(Option<u16>, Option<i8>)> {
 /// Decode movement counter from 1 byte
 /// Range: 0 to 254
 /// Invalid value: 255
-fn decode_movement_counter(byte: u8) -> Option<u8> {
-    if byte == 255 {
-        None // Invalid/not available
+fn decode_movement_counter(bytes: &[u8], offset: usize) -> Result<Option<u8>> {
+    let [raw_value] = field_bytes(bytes, offset, DecodeError::InvalidMovementCounter)?;
+
+    if raw_value == 255 {
+        Ok(None) // Invalid/not available
     } else {
-        Some(byte)
+        Ok(Some(raw_value))
     }
 }
 
 /// Decode measurement sequence number from 2 bytes
 /// Range: 0 to 65534
 /// Invalid value: 65535
-fn decode_measurement_sequence(bytes: &[u8]) -> Result<Option<u16>> {
-    if bytes.len() != 2 {
-        return Err(DecodeError::InvalidLength(
-            "Measurement sequence field must be 2 bytes".into(),
-        ));
-    }
-
-    let raw_value = u16::from_be_bytes([bytes[0], bytes[1]]);
+fn decode_measurement_sequence(bytes: &[u8], offset: usize) -> Result<Option<u16>> {
+    let raw_value = u16::from_be_bytes(field_bytes(
+        bytes,
+        offset,
+        DecodeError::InvalidMeasurementSequence,
+    )?);
 
     if raw_value == 65535 {
         Ok(None) // Invalid/not available
@@ -263,22 +1130,20 @@ fn decode_measurement_sequence(bytes: &[u8]) -> Result<Option<u16>> {
 }
 
 /// Decode MAC address from 6 bytes to lowercase hex string
+///
+/// Unavailable MAC addresses become [`INVALID_MAC_ADDRESS`].
 fn decode_mac_address(bytes: &[u8]) -> String {
-    use std::fmt::Write;
-
-    if bytes.len() != 6 {
-        return "invalid".to_string();
-    }
+    parse_mac_address(bytes).unwrap_or_else(|_| INVALID_MAC_ADDRESS.to_string())
+}
 
-    // Check for invalid MAC (all 0xFF)
-    if bytes.iter().all(|&b| b == 0xFF) {
-        return "invalid".to_string();
+/// Decode MAC address from 6 bytes to lowercase hex string, failing when unavailable
+fn parse_mac_address(bytes: &[u8]) -> Result<String> {
+    // Wrong length or all 0xFF means not available
+    if bytes.len() != 6 || bytes.iter().all(|&b| b == 0xFF) {
+        return Err(DecodeError::InvalidMacAddress(hex::encode(bytes)));
     }
 
-    bytes.iter().fold(String::new(), |mut output, b| {
-        let _ = write!(output, "{b:02x}");
-        output
-    })
+    Ok(mac_hex_into(bytes, &mut [0u8; 12]).to_owned())
 }
 
 #[cfg(test)]
@@ -301,6 +1166,38 @@ mod tests {
         assert_debug_snapshot!(name, res);
     }
 
+    // Pin the extremes independently of the snapshots above.
+    #[rstest]
+    #[case::maximum(
+        "057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F",
+        163.835,
+        163.835,
+        1155.34,
+        32767
+    )]
+    #[case::minimum(
+        "058001000000008001800180010000000000CBB8334C884F",
+        -163.835,
+        0.0,
+        500.0,
+        -32767
+    )]
+    fn decode_extremes(
+        #[case] hex_str: &str,
+        #[case] temperature: f64,
+        #[case] humidity: f64,
+        #[case] pressure: f64,
+        #[case] acceleration: i16,
+    ) {
+        let data = decode(&hex::decode(hex_str).unwrap()).unwrap();
+        assert_eq!(data.temperature, Some(temperature));
+        assert_eq!(data.humidity, Some(humidity));
+        assert_eq!(data.pressure, Some(pressure));
+        assert_eq!(data.acceleration_x, Some(acceleration));
+        assert_eq!(data.acceleration_y, Some(acceleration));
+        assert_eq!(data.acceleration_z, Some(acceleration));
+    }
+
     // Error and boundary checks remain explicit.
     #[test]
     fn decode_errors() {
@@ -323,6 +1220,185 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn field_errors_report_byte_range() {
+        assert_eq!(
+            decode_temperature(&[0x12], 1).unwrap_err().to_string(),
+            "Invalid temperature: bytes 1..3 must be 2 bytes, got 1"
+        );
+        assert_eq!(
+            decode_pressure(&[], 5).unwrap_err().to_string(),
+            "Invalid pressure: bytes 5..7 must be 2 bytes, got 0"
+        );
+        assert_eq!(
+            decode_power_info(&[0x0A, 0xC3, 0x42], 13)
+                .unwrap_err()
+                .to_string(),
+            "Invalid power info: bytes 13..15 must be 2 bytes, got 3"
+        );
+        assert_eq!(
+            decode_measurement_sequence(&[0x00], 16).unwrap_err(),
+            DecodeError::InvalidMeasurementSequence("bytes 16..18 must be 2 bytes, got 1".into())
+        );
+    }
+
+    #[rstest]
+    #[case(decode_humidity(&[0x01], 3).unwrap_err(), DecodeError::InvalidHumidity("bytes 3..5 must be 2 bytes, got 1".into()))]
+    #[case(decode_acceleration(&[], 9).unwrap_err(), DecodeError::InvalidAcceleration("bytes 9..11 must be 2 bytes, got 0".into()))]
+    #[case(decode_movement_counter(&[1, 2], 15).unwrap_err(), DecodeError::InvalidMovementCounter("bytes 15..16 must be 1 bytes, got 2".into()))]
+    fn field_specific_errors(#[case] err: DecodeError, #[case] expected: DecodeError) {
+        assert_eq!(err, expected);
+    }
+
+    #[test]
+    fn pressure_at_sea_level() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode(&raw).unwrap();
+
+        assert_eq!(data.pressure_at_sea_level(0.0), Some(1000.44));
+        let mslp = data.pressure_at_sea_level(100.0).unwrap();
+        assert!((mslp - 1_012.383_618_414).abs() < 1e-6, "{mslp}");
+
+        let missing = DataFormatV5 {
+            pressure: None,
+            ..data
+        };
+        assert_eq!(missing.pressure_at_sea_level(100.0), None);
+    }
+
+    #[test]
+    fn approx_eq_tolerates_float_noise() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode(&raw).unwrap();
+        let noisy = DataFormatV5 {
+            temperature: data.temperature.map(|t| t + 1e-12),
+            pressure: data.pressure.map(|p| p - 1e-12),
+            ..data.clone()
+        };
+
+        assert_ne!(data, noisy);
+        assert!(data.approx_eq(&noisy, 1e-9));
+        assert!(!data.approx_eq(&noisy, 1e-13));
+
+        let missing = DataFormatV5 {
+            humidity: None,
+            ..data.clone()
+        };
+        assert!(!data.approx_eq(&missing, 1.0));
+        assert!(missing.approx_eq(&missing.clone(), 0.0));
+
+        let other_sequence = DataFormatV5 {
+            measurement_sequence: Some(206),
+            ..data.clone()
+        };
+        assert!(!data.approx_eq(&other_sequence, 1.0));
+    }
+
+    #[test]
+    fn acceleration_vector() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode(&raw).unwrap();
+        let acceleration = data.acceleration().unwrap();
+        assert_eq!(
+            acceleration,
+            Acceleration {
+                x: 4,
+                y: -4,
+                z: 1036
+            }
+        );
+        assert_eq!(acceleration.to_g(), [0.004, -0.004, 1.036]);
+        assert_eq!(data.acceleration_g(), Some((0.004, -0.004, 1.036)));
+
+        let missing_y = DataFormatV5 {
+            acceleration_y: None,
+            ..data
+        };
+        assert_eq!(missing_y.acceleration(), None);
+        assert_eq!(missing_y.acceleration_g(), None);
+    }
+
+    #[rstest]
+    #[case(0, 0, 1000, 50, Some(false))]
+    #[case(0, 0, -1000, 50, Some(false))]
+    #[case(4, -4, 1036, 50, Some(false))]
+    #[case(0, 0, 1050, 50, Some(false))] // exactly at the tolerance
+    #[case(0, 0, 1051, 50, Some(true))]
+    #[case(0, 600, 0, 50, Some(true))] // free fall side of 1 g
+    #[case(0, 0, 0, 50, Some(true))]
+    #[case(0, 0, 1036, 0, Some(true))]
+    #[case(0, 0, 1036, -50, Some(false))]
+    fn is_moving(
+        #[case] x: i16,
+        #[case] y: i16,
+        #[case] z: i16,
+        #[case] tolerance: i16,
+        #[case] expected: Option<bool>,
+    ) {
+        let data = DataFormatV5::builder()
+            .acceleration_x(x)
+            .acceleration_y(y)
+            .acceleration_z(z)
+            .build();
+        assert_eq!(data.is_moving(tolerance), expected);
+    }
+
+    #[test]
+    fn is_moving_without_acceleration() {
+        let data = DataFormatV5::builder()
+            .acceleration_x(0)
+            .acceleration_y(0)
+            .build();
+        assert_eq!(data.is_moving(50), None);
+    }
+
+    #[rstest]
+    #[case(0, 0, 0, 0.0)]
+    #[case(0, 0, 1000, 1.0)]
+    #[case(0, 0, -1000, 1.0)]
+    #[case(300, 400, 0, 0.5)]
+    #[case(200, 400, 400, 0.6)]
+    fn acceleration_magnitude(#[case] x: i16, #[case] y: i16, #[case] z: i16, #[case] g: f64) {
+        let acceleration = Acceleration { x, y, z };
+        assert!((acceleration.magnitude() - g * 1000.0).abs() < 1e-9);
+        assert!((acceleration.magnitude_g() - g).abs() < 1e-12);
+    }
+
+    #[test]
+    fn decode_partial_lengths() {
+        let full = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let expected = decode(&full).unwrap();
+
+        // 24 bytes decodes like the strict decoder
+        assert_eq!(decode_partial(&full).unwrap(), expected);
+
+        // 18 bytes keeps every reading, only the MAC is unknown
+        let truncated = decode_partial(&full[..PAYLOAD_LENGTH]).unwrap();
+        assert_eq!(
+            truncated,
+            DataFormatV5 {
+                mac_address: UNKNOWN_MAC_ADDRESS.to_string(),
+                ..expected
+            }
+        );
+
+        assert_eq!(
+            decode_partial(&full[..20]),
+            Err(DecodeError::invalid_length(PAYLOAD_LENGTH, 20))
+        );
+        assert!(matches!(
+            decode(&full[..PAYLOAD_LENGTH]),
+            Err(DecodeError::InvalidLength(_))
+        ));
+
+        let mut wrong_format = full[..PAYLOAD_LENGTH].to_vec();
+        wrong_format[0] = 6;
+        assert_eq!(
+            decode_partial(&wrong_format),
+            Err(DecodeError::UnsupportedFormat(6))
+        );
+    }
+
     // For primitive-returning decoders, use rstest with expected primitive values.
     #[rstest]
     #[case("0000", Some(0.0))]
@@ -331,7 +1407,7 @@ mod tests {
     #[case("8000", None)]
     fn temperature_cases(#[case] hex_str: &str, #[case] expected: Option<f64>) {
         let bytes = hex::decode(hex_str).unwrap();
-        assert_eq!(decode_temperature(&bytes).unwrap(), expected);
+        assert_eq!(decode_temperature(&bytes, 0).unwrap(), expected);
     }
 
     #[rstest]
@@ -341,17 +1417,17 @@ mod tests {
     #[case("FFFF", None)]
     fn humidity_cases(#[case] hex_str: &str, #[case] expected: Option<f64>) {
         let bytes = hex::decode(hex_str).unwrap();
-        assert_eq!(decode_humidity(&bytes).unwrap(), expected);
+        assert_eq!(decode_humidity(&bytes, 0).unwrap(), expected);
     }
 
     #[rstest]
-    #[case("0000", Some(50000.0))]
-    #[case("C855", Some(101285.0))]
-    #[case("FFFE", Some(115534.0))]
+    #[case("0000", Some(500.0))]
+    #[case("C855", Some(1012.85))]
+    #[case("FFFE", Some(1155.34))]
     #[case("FFFF", None)]
     fn pressure_cases(#[case] hex_str: &str, #[case] expected: Option<f64>) {
         let bytes = hex::decode(hex_str).unwrap();
-        assert_eq!(decode_pressure(&bytes).unwrap(), expected);
+        assert_eq!(decode_pressure(&bytes, 0).unwrap(), expected);
     }
 
     #[rstest]
@@ -360,16 +1436,242 @@ mod tests {
     #[case("8000", None)]
     fn acceleration_cases(#[case] hex_str: &str, #[case] expected: Option<i16>) {
         let bytes = hex::decode(hex_str).unwrap();
-        assert_eq!(decode_acceleration(&bytes).unwrap(), expected);
+        assert_eq!(decode_acceleration(&bytes, 0).unwrap(), expected);
     }
 
     #[rstest]
     #[case("0AC3", (Some(1686u16), Some(-34i8)))]
     #[case("FFE0", (None, Some(-40i8)))] // battery invalid; lower bits E0 -> 0 => -40 dBm
     #[case("FFFF", (None, None))]
+    #[case::tx_power_min("0000", (Some(1600u16), Some(-40i8)))]
+    #[case::tx_power_max("001E", (Some(1600u16), Some(20i8)))]
+    #[case::tx_power_invalid("001F", (Some(1600u16), None))]
+    #[case::battery_max("FFDE", (Some(3646u16), Some(20i8)))]
     fn power_info_cases(#[case] hex_str: &str, #[case] expected: (Option<u16>, Option<i8>)) {
         let bytes = hex::decode(hex_str).unwrap();
-        assert_eq!(decode_power_info(&bytes).unwrap(), expected);
+        assert_eq!(decode_power_info(&bytes, 0).unwrap(), expected);
+    }
+
+    #[test]
+    fn pack_power_info_inverts_decode() {
+        for dbm in (-40..=20).step_by(2) {
+            for mv in [1600, 2977, 3646] {
+                let packed = pack_power_info(Some(mv), Some(dbm));
+                assert_eq!(
+                    decode_power_info(&packed.to_be_bytes(), 0).unwrap(),
+                    (Some(mv), Some(dbm))
+                );
+            }
+            let packed = pack_power_info(None, Some(dbm));
+            assert_eq!(packed >> 5, 2047);
+            assert_eq!(
+                decode_power_info(&packed.to_be_bytes(), 0).unwrap(),
+                (None, Some(dbm))
+            );
+        }
+
+        assert_eq!(pack_power_info(Some(1600), None) & 0x1F, 31);
+        assert_eq!(pack_power_info(None, None), u16::MAX);
+    }
+
+    #[rstest]
+    #[case::voltage_low(Some(1599), Some(0), 0xFFF4)]
+    #[case::voltage_high(Some(3647), Some(0), 0xFFF4)]
+    #[case::tx_power_odd(Some(1600), Some(3), 0x001F)]
+    #[case::tx_power_high(Some(1600), Some(22), 0x001F)]
+    #[case::tx_power_low(Some(1600), Some(-42), 0x001F)]
+    fn pack_power_info_unrepresentable(
+        #[case] voltage_mv: Option<u16>,
+        #[case] tx_dbm: Option<i8>,
+        #[case] expected: u16,
+    ) {
+        assert_eq!(pack_power_info(voltage_mv, tx_dbm), expected);
+    }
+
+    // Each multi-byte field gets 0x1234 (or 0xFEDC for signed ones), whose
+    // little-endian reading 0x3412 decodes to a different value.
+    #[rstest]
+    #[case::temperature(1, [0x12, 0x34], |d: &DataFormatV5| d.temperature, 23.3)]
+    #[case::negative_temperature(1, [0xFE, 0xDC], |d: &DataFormatV5| d.temperature, -1.46)]
+    #[case::humidity(3, [0x12, 0x34], |d: &DataFormatV5| d.humidity, 11.65)]
+    #[case::pressure(5, [0x12, 0x34], |d: &DataFormatV5| d.pressure, 546.6)]
+    #[case::acceleration_x(7, [0x12, 0x34], |d: &DataFormatV5| d.acceleration_x.map(f64::from), 4660.0)]
+    #[case::acceleration_y(9, [0xFE, 0xDC], |d: &DataFormatV5| d.acceleration_y.map(f64::from), -292.0)]
+    #[case::acceleration_z(11, [0x12, 0x34], |d: &DataFormatV5| d.acceleration_z.map(f64::from), 4660.0)]
+    #[case::battery_voltage(13, [0x12, 0x34], |d: &DataFormatV5| d.battery_voltage.map(f64::from), 1745.0)]
+    #[case::tx_power(13, [0x12, 0x34], |d: &DataFormatV5| d.tx_power.map(f64::from), 0.0)]
+    #[case::measurement_sequence(16, [0x12, 0x34], |d: &DataFormatV5| d.measurement_sequence.map(f64::from), 4660.0)]
+    fn fields_are_big_endian(
+        #[case] offset: usize,
+        #[case] field_bytes: [u8; 2],
+        #[case] field: fn(&DataFormatV5) -> Option<f64>,
+        #[case] expected: f64,
+    ) {
+        let mut raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        raw[offset..offset + 2].copy_from_slice(&field_bytes);
+        let value = field(&decode(&raw).unwrap()).unwrap();
+        assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+    }
+
+    #[test]
+    fn readings_keys() {
+        let bytes = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode(&bytes).unwrap();
+        let keys: Vec<_> = data.readings().map(|(name, _)| name).collect();
+        assert_eq!(
+            keys,
+            [
+                "mac_address",
+                "temperature",
+                "humidity",
+                "pressure",
+                "acceleration_x",
+                "acceleration_y",
+                "acceleration_z",
+                "battery_voltage",
+                "tx_power",
+                "movement_counter",
+                "measurement_sequence",
+            ]
+        );
+        assert!(
+            data.readings()
+                .any(|reading| reading == ("tx_power", ReadingValue::I8(4)))
+        );
+
+        let bytes = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        let keys: Vec<_> = decode(&bytes)
+            .unwrap()
+            .readings()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(keys, ["mac_address"]);
+    }
+
+    #[rstest]
+    #[case::valid("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F", true)]
+    #[case::invalid("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF", false)]
+    // Only the movement counter is available
+    #[case::movement_only("058000FFFFFFFF800080008000FFFF42FFFFFFFFFFFFFFFF", true)]
+    fn has_any_reading(#[case] raw: &str, #[case] expected: bool) {
+        let data = decode(&hex::decode(raw).unwrap()).unwrap();
+        assert_eq!(data.has_any_reading(), expected);
+    }
+
+    #[rstest]
+    #[case::all_zero("050000000000000000000000000000000000CBB8334C884F", true)]
+    #[case::all_zero_mac("050000000000000000000000000000000000000000000000", true)]
+    // 0°C, 0.5%, 1000 hPa and 1 g on the z axis
+    #[case::near_zero("05000000C8C3500000000003E8AC364200CDCBB8334C884F", false)]
+    #[case::valid("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F", false)]
+    #[case::invalid("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF", false)]
+    fn looks_like_uninitialized(#[case] raw: &str, #[case] expected: bool) {
+        let data = decode(&hex::decode(raw).unwrap()).unwrap();
+        assert_eq!(data.looks_like_uninitialized(), expected);
+    }
+
+    #[rstest]
+    #[case::valid("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::maximum("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F")]
+    #[case::minimum("058001000000008001800180010000000000CBB8334C884F")]
+    #[case::invalid("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF")]
+    fn encode_round_trip(#[case] raw: &str) {
+        let bytes = hex::decode(raw).unwrap();
+        assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn encode_errors() {
+        let data = DataFormatV5::builder().mac_address("cbb8334c884f").build();
+        assert_eq!(
+            encode(&DataFormatV5 {
+                tx_power: Some(3),
+                ..data.clone()
+            }),
+            Err(DecodeError::InvalidPowerInfo(
+                "TX power 3 dBm is not an even value in -40..=20 dBm".into()
+            ))
+        );
+        assert_eq!(
+            encode(&DataFormatV5 {
+                humidity: Some(-1.0),
+                ..data.clone()
+            }),
+            Err(DecodeError::InvalidHumidity(
+                "-1 is outside the encodable range".into()
+            ))
+        );
+        assert_eq!(
+            encode(&DataFormatV5 {
+                mac_address: "cbb833".into(),
+                ..data
+            }),
+            Err(DecodeError::InvalidMacAddress("cbb833".into()))
+        );
+    }
+
+    #[rstest]
+    #[case::valid("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::maximum("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F")]
+    #[case::minimum("058001000000008001800180010000000000CBB8334C884F")]
+    #[case::invalid("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF")]
+    fn view_matches_decode(#[case] raw: &str) {
+        let bytes = hex::decode(raw).unwrap();
+        let data = decode(&bytes).unwrap();
+        let view = view(&bytes).unwrap();
+
+        assert_eq!(view.temperature(), data.temperature);
+        assert_eq!(view.humidity(), data.humidity);
+        assert_eq!(view.pressure(), data.pressure);
+        assert_eq!(view.acceleration_x(), data.acceleration_x);
+        assert_eq!(view.acceleration_y(), data.acceleration_y);
+        assert_eq!(view.acceleration_z(), data.acceleration_z);
+        assert_eq!(view.battery_voltage(), data.battery_voltage);
+        assert_eq!(view.tx_power(), data.tx_power);
+        assert_eq!(view.movement_counter(), data.movement_counter);
+        assert_eq!(view.measurement_sequence(), data.measurement_sequence);
+        assert_eq!(view.mac_address_bytes(), &bytes[18..]);
+        assert_eq!(view.to_owned(), data);
+    }
+
+    #[test]
+    fn view_errors() {
+        assert_eq!(view(&[0x05; 18]), Err(DecodeError::invalid_length(24, 18)));
+        assert_eq!(view(&[0x06; 24]), Err(DecodeError::UnsupportedFormat(6)));
+    }
+
+    #[test]
+    fn typed_mac_address() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(
+            decode(&raw).unwrap().mac(),
+            Some(MacAddress([0xCB, 0xB8, 0x33, 0x4C, 0x88, 0x4F]))
+        );
+
+        let raw = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        assert_eq!(decode(&raw).unwrap().mac(), None);
+    }
+
+    #[test]
+    fn strict_mac_address() {
+        let valid = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode_strict(&valid).unwrap();
+        assert!(data.mac_is_valid());
+        assert_eq!(data, decode(&valid).unwrap());
+
+        let no_mac = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDFFFFFFFFFFFF").unwrap();
+        assert!(!decode(&no_mac).unwrap().mac_is_valid());
+        assert_eq!(
+            decode_strict(&no_mac),
+            Err(DecodeError::InvalidMacAddress("ffffffffffff".into()))
+        );
+
+        assert!(
+            !decode_partial(&valid[..PAYLOAD_LENGTH])
+                .unwrap()
+                .mac_is_valid()
+        );
+        assert!(!DataFormatV5::builder().build().mac_is_valid());
     }
 
     #[rstest]
@@ -379,17 +1681,226 @@ mod tests {
         assert_eq!(decode_mac_address(&input), expected.to_string());
     }
 
+    #[test]
+    fn builder_matches_decoded() {
+        let built = DataFormatV5::builder()
+            .mac_address("cbb8334c884f")
+            .temperature(24.3)
+            .humidity(53.49)
+            .pressure(1000.44)
+            .acceleration_x(4)
+            .acceleration_y(-4)
+            .acceleration_z(1036)
+            .battery_voltage(2977)
+            .tx_power(4)
+            .movement_counter(66)
+            .measurement_sequence(205)
+            .build();
+
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(built, decode(&raw).unwrap());
+    }
+
+    #[test]
+    fn builder_defaults_match_invalid_vector() {
+        let raw = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        assert_eq!(DataFormatV5::builder().build(), decode(&raw).unwrap());
+    }
+
+    #[rstest]
+    #[case("cbb8334c884f")]
+    #[case("CBB8334C884F")]
+    #[case("cb:b8:33:4c:88:4f")]
+    #[case("CB:B8:33:4C:88:4F")]
+    #[case("CB-B8-33-4C-88-4F")]
+    fn builder_mac_address_formats(#[case] mac_address: &str) {
+        let built = DataFormatV5::builder().mac_address(mac_address).build();
+        assert_eq!(built.mac_address, "cbb8334c884f");
+    }
+
+    #[test]
+    fn newer_by_measurement_sequence() {
+        let older = DataFormatV5::builder().measurement_sequence(65_534).build();
+        let newer = DataFormatV5::builder().measurement_sequence(0).build();
+        let unknown = DataFormatV5::builder().build();
+
+        assert_eq!(newer.is_newer_than(&older), Some(true));
+        assert_eq!(older.is_newer_than(&newer), Some(false));
+        assert_eq!(newer.is_newer_than(&newer), Some(false));
+        assert_eq!(newer.is_newer_than(&unknown), None);
+        assert_eq!(unknown.is_newer_than(&older), None);
+    }
+
+    #[test]
+    fn raw_values() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(
+            decode(&raw).unwrap().raw(),
+            RawV5 {
+                temperature: Some(0x12FC),
+                humidity: Some(0x5394),
+                pressure: Some(0xC37C),
+                acceleration_x: Some(4),
+                acceleration_y: Some(-4),
+                acceleration_z: Some(1036),
+                battery_voltage: Some(0xAC36 >> 5),
+                tx_power: Some(0x16),
+                movement_counter: Some(0x42),
+                measurement_sequence: Some(0xCD),
+            }
+        );
+
+        let raw = hex::decode("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F").unwrap();
+        let max = decode(&raw).unwrap().raw();
+        assert_eq!(max.temperature, Some(i16::MAX));
+        assert_eq!(max.humidity, Some(0xFFFE));
+        assert_eq!(max.pressure, Some(0xFFFE));
+
+        let raw = hex::decode("058001000000008001800180010000000000CBB8334C884F").unwrap();
+        assert_eq!(decode(&raw).unwrap().raw().temperature, Some(i16::MIN + 1));
+    }
+
+    #[rstest]
+    #[case(1000, 4, None, Some(22))]
+    #[case(3647, -42, None, None)]
+    #[case(1600, 22, Some(0), None)]
+    #[case(3646, -40, Some(2046), Some(0))]
+    fn raw_out_of_range(
+        #[case] voltage: u16,
+        #[case] tx_power: i8,
+        #[case] expected_voltage: Option<u16>,
+        #[case] expected_tx_power: Option<u8>,
+    ) {
+        let raw = DataFormatV5::builder()
+            .battery_voltage(voltage)
+            .tx_power(tx_power)
+            .build()
+            .raw();
+        assert_eq!(raw.battery_voltage, expected_voltage);
+        assert_eq!(raw.tx_power, expected_tx_power);
+    }
+
+    #[rstest]
+    #[case("0000", Some(50_000.0), Some(500.0))]
+    #[case("C855", Some(101_285.0), Some(1012.85))]
+    #[case("FFFE", Some(115_534.0), Some(1155.34))]
+    #[case("FFFF", None, None)]
+    fn pressure_edges_in_both_units(
+        #[case] raw: &str,
+        #[case] pa: Option<f64>,
+        #[case] hpa: Option<f64>,
+    ) {
+        let v5 = decode(
+            &hex::decode(format!("0512FC5394{raw}0004FFFC040CAC364200CDCBB8334C884F")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(v5.pressure_pa(), pa);
+        assert_eq!(v5.pressure_hpa(), hpa);
+        assert_eq!(v5.pressure, hpa);
+
+        // Same raw value, same hPa as Data Format 6 and E1
+        let v6 = crate::v6::decode(
+            &hex::decode(format!("06170C5668{raw}007000C90501D9FFCD004C884F")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(v6.pressure, hpa);
+        assert_eq!(v6.pressure_pa(), pa);
+        let e1 = crate::e1::decode(
+            &hex::decode(format!(
+                "E1170C5668{raw}0065007004BD11CA00C90A0213E0AC000000DECDEE100000000000CBB8334C884F"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(e1.pressure, hpa);
+        assert_eq!(e1.pressure_pa(), pa);
+    }
+
+    #[test]
+    fn comfort_indexes() {
+        let hot = DataFormatV5::builder()
+            .temperature(32.22)
+            .humidity(70.0)
+            .build();
+        assert_eq!(hot.heat_index_c(), units::heat_index(32.22, 70.0));
+        assert_eq!(hot.humidex(), units::humidex(32.22, 70.0));
+        assert!(hot.heat_index_c().is_some());
+
+        // Decoded 24.3°C is below the heat index range but within humidex
+        let data =
+            decode(&hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap())
+                .unwrap();
+        assert_eq!(data.heat_index_c(), None);
+        assert!(data.humidex().is_some());
+
+        let no_humidity = DataFormatV5::builder().temperature(32.22).build();
+        assert_eq!(no_humidity.heat_index_c(), None);
+        assert_eq!(no_humidity.humidex(), None);
+    }
+
+    #[test]
+    fn pressure_units() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!(data.pressure_hpa(), Some(1000.44));
+        assert_eq!(data.pressure_pa(), Some(100_044.0));
+        assert!((data.pressure_mmhg().unwrap() - 750.391_602_939).abs() < 1e-6);
+        assert!((data.pressure_inhg().unwrap() - 29.542_976_494).abs() < 1e-6);
+
+        let raw = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!(data.pressure_pa(), None);
+        assert_eq!(data.pressure_mmhg(), None);
+    }
+
+    #[test]
+    fn raw_values_honor_invalid_sentinels() {
+        let raw = hex::decode("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF").unwrap();
+        assert_eq!(
+            decode(&raw).unwrap().raw(),
+            RawV5 {
+                temperature: None,
+                humidity: None,
+                pressure: None,
+                acceleration_x: None,
+                acceleration_y: None,
+                acceleration_z: None,
+                battery_voltage: None,
+                tx_power: None,
+                movement_counter: None,
+                measurement_sequence: None,
+            }
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn station_json() {
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        assert_eq!(
+            decode(&raw).unwrap().to_station_json(),
+            r#"{"temperature":24.3,"humidity":53.49,"pressure":1000.44,"accelerationX":4,"accelerationY":-4,"accelerationZ":1036,"voltage":2.977,"txPower":4,"movementCounter":66,"measurementSequenceNumber":205}"#
+        );
+
+        // Unavailable readings are omitted
+        let partial = DataFormatV5::builder().temperature(21.5).build();
+        assert_eq!(partial.to_station_json(), r#"{"temperature":21.5}"#);
+    }
+
     #[test]
     fn movement_and_sequence_boundaries() {
         // Movement counter boundary
-        assert_eq!(decode_movement_counter(254), Some(254));
-        assert_eq!(decode_movement_counter(255), None);
+        assert_eq!(decode_movement_counter(&[254], 15).unwrap(), Some(254));
+        assert_eq!(decode_movement_counter(&[255], 15).unwrap(), None);
 
         // Measurement sequence boundary
         assert_eq!(
-            decode_measurement_sequence(&[0xFF, 0xFE]).unwrap(),
+            decode_measurement_sequence(&[0xFF, 0xFE], 16).unwrap(),
             Some(65534)
         );
-        assert_eq!(decode_measurement_sequence(&[0xFF, 0xFF]).unwrap(), None);
+        assert_eq!(
+            decode_measurement_sequence(&[0xFF, 0xFF], 16).unwrap(),
+            None
+        );
     }
 }
@@ -1,4 +1,11 @@
+use crate::air_quality;
+use crate::encode;
 use crate::error::{DecodeError, Result};
+use crate::flags::Flags;
+use crate::mac::ShortMacAddress;
+use crate::readings::{self, ReadingValue};
+use crate::sequence;
+use crate::units::{self, Luminosity};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
 
@@ -8,6 +15,7 @@ pub const PAYLOAD_WITH_MAC_LENGTH: usize = PAYLOAD_LENGTH + 3; // 3 for compactn
 
 /// Data Format 6 (`RAWv3`) structure, as specified in the Ruuvi v6 XML spec.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "camel-case", serde(rename_all = "camelCase"))]
 pub struct DataFormatV6 {
     /// Temperature in Celsius (-163.835 to +163.835°C, 0.005°C resolution)
     pub temperature: Option<f64>,
@@ -16,12 +24,13 @@ pub struct DataFormatV6 {
     /// Pressure in hPa (500.00 to 1155.35 hPa, 1 Pa resolution, offset -50000 Pa)
     pub pressure: Option<f64>,
     /// PM2.5 in μg/m³ (0.1 μg/m³ resolution, 0..1000 μg/m³)
+    #[cfg_attr(feature = "camel-case", serde(rename = "pm25"))]
     pub pm2_5: Option<f64>,
     /// CO2 concentration in ppm (1 ppm resolution, 0..40000 ppm)
     pub co2: Option<u16>,
-    /// VOC index, unitless (1/bit, 0..500, 9 bits: 8 from byte + 1 from flags)
+    /// VOC index, unitless (1/bit, 1..500, 9 bits: 8 from byte + 1 from flags)
     pub voc_index: Option<u16>,
-    /// `NOx` index, unitless (1/bit, 0..500, 9 bits: 8 from byte + 1 from flags)
+    /// `NOx` index, unitless (1/bit, 1..500, 9 bits: 8 from byte + 1 from flags)
     pub nox_index: Option<u16>,
     /// Luminosity, Lux (logarithmic, see spec for decoding)
     pub luminosity: Option<f64>,
@@ -32,9 +41,201 @@ pub struct DataFormatV6 {
     /// Flags byte (bitfield, raw)
     pub flags: u8,
     /// Lowest 3 bytes of device MAC address (3 bytes, 24 bits)
+    #[cfg_attr(
+        feature = "mac-colons",
+        serde(
+            serialize_with = "crate::mac::serialize_colon_uppercase",
+            deserialize_with = "crate::mac::deserialize_bare"
+        )
+    )]
     pub mac_address: String,
 }
 
+impl DataFormatV6 {
+    /// Heat index in Celsius, see [`units::heat_index`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range the index is defined for.
+    #[must_use]
+    pub fn heat_index_c(&self) -> Option<f64> {
+        units::heat_index(self.temperature?, self.humidity?)
+    }
+
+    /// Humidex, see [`units::humidex`]
+    ///
+    /// `None` unless temperature and humidity are available and within the
+    /// range humidex is defined for.
+    #[must_use]
+    pub fn humidex(&self) -> Option<f64> {
+        units::humidex(self.temperature?, self.humidity?)
+    }
+
+    /// Copy with the linear float fields snapped to their resolution
+    ///
+    /// Luminosity is logarithmic and left as decoded.
+    #[must_use]
+    pub fn rounded(&self) -> Self {
+        Self {
+            temperature: self
+                .temperature
+                .map(|t| units::snap_to_resolution(t, 200.0)),
+            humidity: self.humidity.map(|h| units::snap_to_resolution(h, 400.0)),
+            pressure: self.pressure.map(|p| units::snap_to_resolution(p, 100.0)),
+            pm2_5: self.pm2_5.map(|pm| units::snap_to_resolution(pm, 10.0)),
+            ..self.clone()
+        }
+    }
+
+    /// Pressure in Pa, rounded to the 1 Pa resolution of the format
+    #[must_use]
+    pub fn pressure_pa(&self) -> Option<f64> {
+        self.pressure.map(|hpa| units::hpa_to_pa(hpa).round())
+    }
+
+    /// Pressure in hPa, same as the `pressure` field
+    #[must_use]
+    pub fn pressure_hpa(&self) -> Option<f64> {
+        self.pressure
+    }
+
+    /// Pressure in mmHg
+    #[must_use]
+    pub fn pressure_mmhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_mmhg)
+    }
+
+    /// Pressure in inHg
+    #[must_use]
+    pub fn pressure_inhg(&self) -> Option<f64> {
+        self.pressure.map(units::hpa_to_inhg)
+    }
+
+    /// Pressure reduced to mean sea level in hPa, for a station at `altitude_m` metres
+    ///
+    /// See [`units::sea_level_pressure`].
+    #[must_use]
+    pub fn pressure_at_sea_level(&self, altitude_m: f64) -> Option<f64> {
+        self.pressure
+            .map(|hpa| units::sea_level_pressure(hpa, altitude_m))
+    }
+
+    /// Check whether this reading was measured after `other`, handling counter wraparound
+    ///
+    /// Returns `None` when either measurement sequence is unavailable.
+    #[must_use]
+    pub fn is_newer_than(&self, other: &Self) -> Option<bool> {
+        let current = self.measurement_sequence?;
+        let other = other.measurement_sequence?;
+        Some(sequence::is_newer(
+            u32::from(current),
+            u32::from(other),
+            sequence::V6_SEQUENCE_MODULUS,
+        ))
+    }
+
+    /// Available fields as `(name, value)` pairs, skipping `None` fields
+    ///
+    /// Names match the struct fields.
+    pub fn readings(&self) -> impl Iterator<Item = (&'static str, ReadingValue)> {
+        readings::available([
+            ("temperature", self.temperature.map(ReadingValue::F64)),
+            ("humidity", self.humidity.map(ReadingValue::F64)),
+            ("pressure", self.pressure.map(ReadingValue::F64)),
+            ("pm2_5", self.pm2_5.map(ReadingValue::F64)),
+            ("co2", self.co2.map(ReadingValue::U16)),
+            ("voc_index", self.voc_index.map(ReadingValue::U16)),
+            ("nox_index", self.nox_index.map(ReadingValue::U16)),
+            ("luminosity", self.luminosity.map(ReadingValue::F64)),
+            ("reserved", self.reserved.map(ReadingValue::U8)),
+            (
+                "measurement_sequence",
+                self.measurement_sequence.map(ReadingValue::U8),
+            ),
+            ("flags", Some(ReadingValue::U8(self.flags))),
+            (
+                "mac_address",
+                Some(ReadingValue::String(self.mac_address.clone())),
+            ),
+        ])
+    }
+
+    /// Whether `other` reports the same physical conditions
+    ///
+    /// Compares the sensor fields only, ignoring `mac_address`,
+    /// `measurement_sequence`, `flags` and `reserved`.
+    #[must_use]
+    pub fn same_measurement(&self, other: &Self) -> bool {
+        self.temperature == other.temperature
+            && self.humidity == other.humidity
+            && self.pressure == other.pressure
+            && self.pm2_5 == other.pm2_5
+            && self.co2 == other.co2
+            && self.voc_index == other.voc_index
+            && self.nox_index == other.nox_index
+            && self.luminosity == other.luminosity
+    }
+
+    /// Decode the status bits of the `flags` byte
+    #[must_use]
+    pub fn flags_decoded(&self) -> Flags {
+        Flags::from(self.flags)
+    }
+
+    /// CO2 level classification, `None` when CO2 is unavailable
+    #[must_use]
+    pub fn co2_level(&self) -> Option<air_quality::Co2Level> {
+        self.co2.map(air_quality::co2_level)
+    }
+
+    /// The higher of the raw VOC and `NOx` indexes
+    ///
+    /// The two indexes have different baselines, see
+    /// [`air_quality::combined_gas_index`].
+    #[must_use]
+    pub fn combined_gas_index(&self) -> Option<u16> {
+        air_quality::combined_gas_index(self.voc_index, self.nox_index)
+    }
+
+    /// Full 6-byte MAC address as lowercase hex, reconstructed from a known OUI prefix
+    ///
+    /// Data Format 6 only carries the lowest 3 bytes of the MAC address. When the
+    /// upper 3 bytes are known, e.g. from a Data Format 5 advertisement of the same
+    /// device, this gives the same MAC address string as [`crate::v5::DataFormatV5`].
+    ///
+    /// # Arguments
+    ///
+    /// * `oui` - Upper 3 bytes of the device MAC address
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ruuvi_decoders::v6;
+    ///
+    /// let bytes = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+    /// let data = v6::decode(&bytes).unwrap();
+    /// assert_eq!(data.full_mac([0xCB, 0xB8, 0x33]), "cbb8334c884f");
+    /// ```
+    #[must_use]
+    pub fn full_mac(&self, oui: [u8; 3]) -> String {
+        format!("{}{}", hex::encode(oui), self.mac_address)
+    }
+
+    /// The lower 3 bytes of the MAC address as a [`ShortMacAddress`]
+    ///
+    /// `None` if `mac_address` was set to something other than 6 hex digits.
+    #[must_use]
+    pub fn mac(&self) -> Option<ShortMacAddress> {
+        self.mac_address.parse().ok()
+    }
+}
+
+impl Luminosity for DataFormatV6 {
+    /// Logarithmic byte code, already converted to lux when decoding
+    fn luminosity_lux(&self) -> Option<f64> {
+        self.luminosity
+    }
+}
+
 /// Decode Data Format 6 payload from raw bytes
 ///
 /// # Arguments
@@ -78,7 +279,7 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatV6> {
 
     // Humidity: 0.0025%/bit, u16, bytes 3-4
     let raw_humidity = get_u16(3);
-    let humidity = if raw_humidity > 40000 {
+    let humidity = if raw_humidity == 65535 {
         None
     } else {
         Some(f64::from(raw_humidity) * 0.0025)
@@ -108,39 +309,15 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatV6> {
     // VOC index: 9 bits, bytes 11 (hi) + flags b6 (LSB)
     let raw_voc_hi = u16::from(bytes[11]);
     let voc_flag = (u16::from(bytes[16]) & 0b0100_0000) >> 6;
-    let voc_index = {
-        let value = (raw_voc_hi << 1) | voc_flag;
-        if value > 500 { None } else { Some(value) }
-    };
+    let voc_index = air_quality::gas_index((raw_voc_hi << 1) | voc_flag);
 
     // NOx index: 9 bits, bytes 12 (hi) + flags b7 (LSB)
     let raw_nox_hi = u16::from(bytes[12]);
     let nox_flag = (u16::from(bytes[16]) & 0b1000_0000) >> 7;
-    let nox_index = {
-        let value = (raw_nox_hi << 1) | nox_flag;
-        if value > 500 { None } else { Some(value) }
-    };
+    let nox_index = air_quality::gas_index((raw_nox_hi << 1) | nox_flag);
 
     // Luminosity: logarithmic, byte 13
-    let raw_lum = bytes[13];
-    let luminosity = if raw_lum == 255 {
-        None
-    } else {
-        // MAX_VALUE := 65535
-        // MAX_CODE  := 254
-        // DELTA     := ln(MAX_VALUE + 1) / MAX_CODE
-
-        // Encoding would be
-        // CODE      := round(ln(value + 1) / DELTA)
-
-        const MAX_VALUE: f64 = 65535.0;
-        const MAX_CODE: f64 = 254.0;
-        let delta: f64 = (MAX_VALUE + 1.0_f64).ln() / MAX_CODE;
-        // Decoding:
-        // VALUE     := exp(CODE * delta) - 1
-        let value = (f64::from(raw_lum).round() * delta).exp() - 1.0;
-        Some(value.min(MAX_VALUE))
-    };
+    let luminosity = decode_luminosity(bytes[13]);
 
     // Reserved: byte 14
     let reserved = Some(bytes[14]);
@@ -175,6 +352,95 @@ pub fn decode(bytes: &[u8]) -> Result<DataFormatV6> {
     })
 }
 
+/// Encode a reading back into a 20 byte Data Format 6 payload
+///
+/// This is the inverse of [`decode`]: `None` fields become their "not
+/// available" sentinels, and the low bits of VOC and `NOx` replace bits 6 and 7
+/// of `flags`. Float fields are rounded to the resolution of the format.
+///
+/// # Example
+///
+/// ```rust
+/// use ruuvi_decoders::v6::{decode, encode};
+///
+/// let bytes = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+/// assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+/// ```
+///
+/// # Errors
+///
+/// * `DecodeError::InvalidTemperature`, `InvalidHumidity` or `InvalidPressure` -
+///   Value cannot be represented in the format
+/// * `DecodeError::InvalidData` - Air quality or luminosity value is out of range
+/// * `DecodeError::InvalidMacAddress` - `mac_address` is not 6 hex digits
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn encode(data: &DataFormatV6) -> Result<Vec<u8>> {
+    let pm2_5 = match data.pm2_5 {
+        Some(pm2_5) => encode::quantize(pm2_5, 0.1, 0..=10000, |value| {
+            DecodeError::invalid_field("PM2.5", &value)
+        })? as u16,
+        None => u16::MAX,
+    };
+    let co2 = match data.co2 {
+        Some(co2 @ 0..=40000) => co2,
+        Some(co2) => return Err(DecodeError::invalid_field("CO2", &co2.to_string())),
+        None => u16::MAX,
+    };
+    let (voc, nox, voc_nox_flags) = encode::pack_voc_nox(
+        encode::gas_index("VOC", data.voc_index)?,
+        encode::gas_index("NOx", data.nox_index)?,
+    );
+    let flags = (data.flags & 0b0011_1111) | voc_nox_flags;
+
+    let mut bytes = Vec::with_capacity(PAYLOAD_WITH_MAC_LENGTH);
+    bytes.push(6);
+    bytes.extend(encode::temperature(data.temperature)?);
+    bytes.extend(encode::humidity(data.humidity)?);
+    bytes.extend(encode::pressure(data.pressure)?);
+    bytes.extend(pm2_5.to_be_bytes());
+    bytes.extend(co2.to_be_bytes());
+    bytes.push(voc);
+    bytes.push(nox);
+    bytes.push(encode_luminosity(data.luminosity)?);
+    bytes.push(data.reserved.unwrap_or(u8::MAX));
+    bytes.push(data.measurement_sequence.unwrap_or(u8::MAX));
+    bytes.push(flags);
+    bytes.extend(encode::mac_address::<3>(&data.mac_address)?);
+    Ok(bytes)
+}
+
+/// Encode luminosity in Lux into the logarithmic code, `None` as 255
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn encode_luminosity(value: Option<f64>) -> Result<u8> {
+    match value {
+        Some(lux) if (0.0..=LUMINOSITY_MAX_VALUE).contains(&lux) => {
+            Ok(((lux + 1.0).ln() / LUMINOSITY_DELTA).round() as u8)
+        }
+        Some(lux) => Err(DecodeError::invalid_field("luminosity", &lux.to_string())),
+        None => Ok(u8::MAX),
+    }
+}
+
+/// Highest luminosity in Lux the logarithmic encoding can represent
+pub(crate) const LUMINOSITY_MAX_VALUE: f64 = 65535.0;
+/// Highest valid luminosity code, 255 marks an invalid reading
+const LUMINOSITY_MAX_CODE: u8 = 254;
+/// Step between codes: `ln(MAX_VALUE + 1) / MAX_CODE`, where `ln(65536) = 16 * ln(2)`
+pub(crate) const LUMINOSITY_DELTA: f64 = 16.0 * std::f64::consts::LN_2 / LUMINOSITY_MAX_CODE as f64;
+
+/// Decode the logarithmic luminosity code into Lux
+///
+/// Encoding: `CODE := round(ln(VALUE + 1) / DELTA)`
+/// Decoding: `VALUE := exp(CODE * DELTA) - 1`
+fn decode_luminosity(code: u8) -> Option<f64> {
+    if code > LUMINOSITY_MAX_CODE {
+        return None;
+    }
+
+    let value = (f64::from(code) * LUMINOSITY_DELTA).exp() - 1.0;
+    Some(value.min(LUMINOSITY_MAX_VALUE))
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -194,6 +460,192 @@ mod tests {
         assert_debug_snapshot!(name, res);
     }
 
+    #[rstest]
+    #[case(0, Some(0.0))]
+    #[case(1, Some(0.044_630_061_412_595_6))]
+    #[case(127, Some(255.0))] // half way: exp(8 * ln 2) - 1
+    #[case(200, Some(6_200.601_875_586_342))]
+    #[case(254, Some(65_535.0))]
+    #[case(255, None)]
+    fn test_luminosity_curve(#[case] code: u8, #[case] expected: Option<f64>) {
+        match (decode_luminosity(code), expected) {
+            (Some(got), Some(expected)) => assert!(
+                (got - expected).abs() < 1e-6,
+                "code {code}: got {got}, expected {expected}"
+            ),
+            (got, expected) => assert_eq!(got, expected),
+        }
+    }
+
+    #[test]
+    fn test_luminosity_encode_inverts_decode() {
+        for code in 0..=u8::MAX {
+            assert_eq!(encode_luminosity(decode_luminosity(code)), Ok(code));
+        }
+    }
+
+    #[rstest]
+    #[case::valid("06170C5668C79E007000C90501D9FFCD004C884F")]
+    #[case::maximum("067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F")]
+    #[case::minimum("06800100000000000000000000000000C04C884F")]
+    fn test_encode_round_trip(#[case] hex_str: &str) {
+        let raw = hex::decode(hex_str).unwrap();
+        assert_eq!(encode(&decode(&raw).unwrap()).unwrap(), raw);
+    }
+
+    #[rstest]
+    #[case(0x05, 0x01, 0x00, Some(10), Some(2))]
+    #[case(0x05, 0x01, 0x40, Some(11), Some(2))] // bit 6 is the VOC LSB
+    #[case(0x05, 0x01, 0x80, Some(10), Some(3))] // bit 7 is the NOx LSB
+    #[case(0x00, 0x00, 0xC0, Some(1), Some(1))]
+    #[case(0x00, 0x00, 0xFF, Some(1), Some(1))] // other flag bits are ignored
+    #[case(0x00, 0x00, 0x3F, None, None)] // 0 means not available yet
+    #[case(0xFA, 0xFA, 0x00, Some(500), Some(500))]
+    #[case(0xFA, 0xFA, 0xC0, None, None)] // 501 is out of range
+    #[case(0xFF, 0xFF, 0xC0, None, None)]
+    fn test_voc_nox_reconstruction(
+        #[case] voc: u8,
+        #[case] nox: u8,
+        #[case] flags: u8,
+        #[case] voc_index: Option<u16>,
+        #[case] nox_index: Option<u16>,
+    ) {
+        let raw = hex::decode(format!(
+            "06170C5668C79E007000C9{voc:02X}{nox:02X}D9FFCD{flags:02X}4C884F"
+        ))
+        .unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!((data.voc_index, data.nox_index), (voc_index, nox_index));
+
+        if let (Some(voc_index), Some(nox_index)) = (voc_index, nox_index) {
+            let (voc_hi, nox_hi, flag_bits) = encode::pack_voc_nox(voc_index, nox_index);
+            assert_eq!((voc_hi, nox_hi, flag_bits), (voc, nox, flags & 0xC0));
+        }
+    }
+
+    #[test]
+    fn test_encode_out_of_range() {
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        let data = decode(&raw).unwrap();
+        assert_eq!(
+            encode(&DataFormatV6 {
+                voc_index: Some(501),
+                ..data.clone()
+            }),
+            Err(DecodeError::invalid_field("VOC", "501"))
+        );
+        assert_eq!(
+            encode(&DataFormatV6 {
+                mac_address: "cbb8334c884f".into(),
+                ..data
+            }),
+            Err(DecodeError::InvalidMacAddress("cbb8334c884f".into()))
+        );
+    }
+
+    // 0x1234 reads as 0x3412 little-endian, so a swapped field decodes differently
+    #[rstest]
+    #[case::temperature(1, [0x12, 0x34], |d: &DataFormatV6| d.temperature, 23.3)]
+    #[case::negative_temperature(1, [0xFE, 0xDC], |d: &DataFormatV6| d.temperature, -1.46)]
+    #[case::humidity(3, [0x12, 0x34], |d: &DataFormatV6| d.humidity, 11.65)]
+    #[case::pressure(5, [0x12, 0x34], |d: &DataFormatV6| d.pressure, 546.6)]
+    #[case::pm2_5(7, [0x12, 0x34], |d: &DataFormatV6| d.pm2_5, 466.0)]
+    #[case::co2(9, [0x12, 0x34], |d: &DataFormatV6| d.co2.map(f64::from), 4660.0)]
+    fn test_fields_are_big_endian(
+        #[case] offset: usize,
+        #[case] field_bytes: [u8; 2],
+        #[case] field: fn(&DataFormatV6) -> Option<f64>,
+        #[case] expected: f64,
+    ) {
+        let mut raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        raw[offset..offset + 2].copy_from_slice(&field_bytes);
+        let value = field(&decode(&raw).unwrap()).unwrap();
+        assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_is_newer_than() {
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        let older = decode(&raw).unwrap();
+        let newer = DataFormatV6 {
+            measurement_sequence: Some(older.measurement_sequence.unwrap().wrapping_add(100)),
+            ..older.clone()
+        };
+
+        assert_eq!(newer.measurement_sequence, Some(49)); // wrapped past 255
+        assert_eq!(newer.is_newer_than(&older), Some(true));
+        assert_eq!(older.is_newer_than(&newer), Some(false));
+    }
+
+    #[test]
+    fn test_flags_decoded() {
+        let raw = hex::decode("067FFF9C40FFFE27109C40FAFAFEFFFF074C8F4F").unwrap();
+        let flags = decode(&raw).unwrap().flags_decoded();
+        assert!(flags.calibration_in_progress);
+        assert!(flags.button_pressed);
+        assert!(flags.rtc_running);
+
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        assert_eq!(decode(&raw).unwrap().flags_decoded(), Flags::default());
+    }
+
+    #[test]
+    fn test_mac() {
+        let bytes = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        let data = decode(&bytes).unwrap();
+        assert_eq!(data.mac(), Some(ShortMacAddress::from([0x4C, 0x88, 0x4F])));
+
+        let unset = DataFormatV6 {
+            mac_address: String::new(),
+            ..data
+        };
+        assert_eq!(unset.mac(), None);
+    }
+
+    #[test]
+    fn test_full_mac_matches_v5() {
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        let v6 = decode(&raw).unwrap();
+        let raw = hex::decode("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F").unwrap();
+        let v5 = crate::v5::decode(&raw).unwrap();
+
+        assert_eq!(v6.full_mac([0xCB, 0xB8, 0x33]), v5.mac_address);
+        assert_eq!(v6.full_mac([0x00, 0x01, 0x0A]), "00010a4c884f");
+    }
+
+    #[test]
+    fn test_combined_gas_index() {
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        // VOC index 10, NOx index 2
+        assert_eq!(decode(&raw).unwrap().combined_gas_index(), Some(10));
+    }
+
+    #[rstest]
+    #[case("9C40", Some(100.0))] // 40000
+    #[case("9C41", Some(100.0025))] // 40001, above 100% is still a reading
+    #[case("FFFE", Some(163.835))]
+    #[case("FFFF", None)]
+    fn test_humidity_boundaries(#[case] raw: &str, #[case] expected: Option<f64>) {
+        let hex_str = format!("06170C{raw}C79E007000C90501D9FFCD004C884F");
+        let humidity = decode(&hex::decode(hex_str).unwrap()).unwrap().humidity;
+
+        match (humidity, expected) {
+            (Some(got), Some(want)) => assert!((got - want).abs() < 1e-9, "{got} != {want}"),
+            (got, want) => assert_eq!(got, want),
+        }
+    }
+
+    #[test]
+    fn test_co2_level() {
+        let raw = hex::decode("06170C5668C79E007000C90501D9FFCD004C884F").unwrap();
+        let data = decode(&raw).unwrap();
+        // 201 ppm
+        assert_eq!(data.co2_level(), Some(air_quality::Co2Level::Good));
+
+        let missing = DataFormatV6 { co2: None, ..data };
+        assert_eq!(missing.co2_level(), None);
+    }
+
     #[test]
     fn test_decode_invalid_length() {
         let bytes: [u8; 10] = [0; 10];
@@ -0,0 +1,92 @@
+//! Physical plausibility checks for decoded readings
+//!
+//! The decoders only reject values the format itself marks as invalid. These
+//! checks additionally reject readings outside what the sensors can physically
+//! report, see [`crate::decode_validated`].
+
+use crate::error::{DecodeError, Result};
+use crate::ruuvi_data::RuuviData;
+
+/// Lowest plausible temperature in °C (sensor operating range)
+pub const TEMPERATURE_MIN: f64 = -40.0;
+/// Highest plausible temperature in °C (sensor operating range)
+pub const TEMPERATURE_MAX: f64 = 85.0;
+/// Lowest plausible relative humidity in %
+pub const HUMIDITY_MIN: f64 = 0.0;
+/// Highest plausible relative humidity in %, allowing for slight overshoot
+pub const HUMIDITY_MAX: f64 = 100.1;
+/// Lowest plausible pressure in hPa
+pub const PRESSURE_MIN: f64 = 300.0;
+/// Highest plausible pressure in hPa
+pub const PRESSURE_MAX: f64 = 1100.0;
+
+/// Check that the present readings are physically plausible
+///
+/// Missing readings (`None`) are not an error.
+///
+/// # Errors
+///
+/// * `DecodeError::ValidationFailed` - A reading is outside its plausible range
+pub fn validate(data: &RuuviData) -> Result<()> {
+    let (temperature, humidity, pressure) = match data {
+        RuuviData::V5(v5) => (v5.temperature, v5.humidity, v5.pressure),
+        RuuviData::V6(v6) => (v6.temperature, v6.humidity, v6.pressure),
+        RuuviData::E1(e1) => (e1.temperature, e1.humidity, e1.pressure),
+    };
+
+    check_range("temperature", temperature, TEMPERATURE_MIN, TEMPERATURE_MAX)?;
+    check_range("humidity", humidity, HUMIDITY_MIN, HUMIDITY_MAX)?;
+    check_range("pressure", pressure, PRESSURE_MIN, PRESSURE_MAX)
+}
+
+fn check_range(field: &str, value: Option<f64>, min: f64, max: f64) -> Result<()> {
+    match value {
+        Some(value) if !(min..=max).contains(&value) => Err(DecodeError::ValidationFailed(
+            format!("{field} {value} outside {min}..={max}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v5;
+    use rstest::rstest;
+
+    fn v5_data(hex_str: &str) -> RuuviData {
+        RuuviData::V5(v5::decode(&hex::decode(hex_str).unwrap()).unwrap())
+    }
+
+    #[rstest]
+    #[case::valid("0512FC5394C37C0004FFFC040CAC364200CDCBB8334C884F")]
+    #[case::sea_level("0500004E20C8550000000000000000000001CBB8334C884F")]
+    #[case::invalid("058000FFFFFFFF800080008000FFFFFFFFFFFFFFFFFFFFFF")]
+    fn plausible(#[case] hex_str: &str) {
+        assert_eq!(validate(&v5_data(hex_str)), Ok(()));
+    }
+
+    #[rstest]
+    #[case::maximum("057FFFFFFEFFFE7FFF7FFF7FFFFFDEFEFFFECBB8334C884F", "temperature")]
+    #[case::minimum("058001000000008001800180010000000000CBB8334C884F", "temperature")]
+    #[case::humidity("0512FCFFFEC37C0004FFFC040CAC364200CDCBB8334C884F", "humidity")]
+    #[case::high_pressure("0512FC5394FFFE0004FFFC040CAC364200CDCBB8334C884F", "pressure")]
+    fn implausible(#[case] hex_str: &str, #[case] field: &str) {
+        match validate(&v5_data(hex_str)) {
+            Err(DecodeError::ValidationFailed(msg)) => assert!(msg.starts_with(field), "{msg}"),
+            other => panic!("Expected ValidationFailed for {field}, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case(100.1, true)]
+    #[case(100.2, false)]
+    #[case(0.0, true)]
+    #[case(-0.1, false)]
+    fn humidity_boundary(#[case] humidity: f64, #[case] ok: bool) {
+        assert_eq!(
+            check_range("humidity", Some(humidity), HUMIDITY_MIN, HUMIDITY_MAX).is_ok(),
+            ok
+        );
+    }
+}
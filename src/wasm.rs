@@ -0,0 +1,27 @@
+//! WebAssembly bindings, enabled with the `wasm` feature
+//!
+//! Decoded data is returned as plain JS objects via `serde-wasm-bindgen`.
+//! Decoding errors are thrown as JS exceptions carrying the `DecodeError`
+//! message.
+
+use wasm_bindgen::prelude::*;
+
+/// Decode a Ruuvi BLE advertisement hex string into a JS object
+///
+/// # Errors
+///
+/// Throws the `DecodeError` message when decoding fails
+#[wasm_bindgen(js_name = decode)]
+pub fn decode_js(hex: &str) -> Result<JsValue, JsError> {
+    let data = crate::decode(hex)?;
+    Ok(serde_wasm_bindgen::to_value(&data)?)
+}
+
+/// Extract the Ruuvi payload hex from a full BLE advertisement
+///
+/// Returns `undefined` when no Ruuvi data is found.
+#[wasm_bindgen(js_name = extractRuuviFromBle)]
+#[must_use]
+pub fn extract_ruuvi_from_ble_js(ble_data: &str) -> Option<String> {
+    crate::extract_ruuvi_from_ble(ble_data)
+}